@@ -0,0 +1,239 @@
+//! Derive macros for `plugin-interfaces`
+//!
+//! Use them through the `derive` feature of `plugin-interfaces`:
+//!
+//! ```ignore
+//! use plugin_interfaces::pluginui::SettingsUi;
+//! use plugin_interfaces::PluginSettings;
+//!
+//! #[derive(PluginSettings, Default)]
+//! struct ChatSettings {
+//!     model: Model,
+//!     #[settings(label = "Temperature", min = 0.0, max = 2.0, step = 0.1)]
+//!     temperature: f64,
+//!     max_tokens: Option<u32>,
+//!     stream: bool,
+//!     #[settings(skip)]
+//!     cache: Vec<String>,
+//! }
+//!
+//! #[derive(PluginSettings, Default, Clone, Copy, PartialEq)]
+//! enum Model {
+//!     #[default]
+//!     #[settings(label = "GPT-4o")]
+//!     Gpt4o,
+//!     Claude,
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Fields, LitFloat, LitInt,
+    LitStr, Token,
+};
+
+/// Derive a settings UI
+///
+/// - On a struct with named fields, implements `SettingsUi` by rendering every
+///   field through `SettingsField` (`String`, `bool`, numbers, `Option<T>` and
+///   enums deriving `PluginSettings`).
+/// - On an enum with only unit variants, implements `SettingsField` as a combo box.
+///
+/// Supported attributes: `#[settings(skip)]`, `#[settings(label = "...")]`
+/// and, for numbers, `#[settings(min = .., max = .., step = ..)]`.
+#[proc_macro_derive(PluginSettings, attributes(settings))]
+pub fn derive_plugin_settings(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let result = match &input.data {
+        Data::Struct(data) => derive_struct(&input, &data.fields),
+        Data::Enum(data) => derive_enum(&input, data.variants.iter().collect()),
+        Data::Union(_) => Err(syn::Error::new(
+            input.span(),
+            "PluginSettings cannot be derived for unions",
+        )),
+    };
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Options collected from `#[settings(...)]`
+#[derive(Default)]
+struct SettingsAttr {
+    skip: bool,
+    label: Option<String>,
+    min: Option<f64>,
+    max: Option<f64>,
+    step: Option<f64>,
+}
+
+impl SettingsAttr {
+    fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = SettingsAttr::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("settings")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    result.skip = true;
+                } else if meta.path.is_ident("label") {
+                    result.label = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("min") {
+                    result.min = Some(parse_number(meta.value()?)?);
+                } else if meta.path.is_ident("max") {
+                    result.max = Some(parse_number(meta.value()?)?);
+                } else if meta.path.is_ident("step") {
+                    result.step = Some(parse_number(meta.value()?)?);
+                } else {
+                    return Err(meta.error("unsupported settings attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+/// Parse an optionally negated integer or float literal
+fn parse_number(input: syn::parse::ParseStream) -> syn::Result<f64> {
+    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+    let value = if input.peek(LitFloat) {
+        input.parse::<LitFloat>()?.base10_parse::<f64>()?
+    } else {
+        input.parse::<LitInt>()?.base10_parse::<f64>()?
+    };
+    Ok(if negative { -value } else { value })
+}
+
+/// Turn `max_tokens` into "Max tokens" and `DarkMode` into "Dark mode"
+fn humanize(ident: &str) -> String {
+    let mut words = String::new();
+    for (i, ch) in ident.trim_start_matches("r#").chars().enumerate() {
+        if ch == '_' {
+            words.push(' ');
+        } else if ch.is_uppercase() && i > 0 {
+            words.push(' ');
+            words.extend(ch.to_lowercase());
+        } else {
+            words.push(ch);
+        }
+    }
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn quote_option(value: Option<f64>) -> TokenStream2 {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(#value)),
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> syn::Result<TokenStream2> {
+    let Fields::Named(fields) = fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "PluginSettings can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut field_widgets = Vec::new();
+    for field in &fields.named {
+        let attr = SettingsAttr::from_attrs(&field.attrs)?;
+        if attr.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let label = attr.label.unwrap_or_else(|| humanize(&ident.to_string()));
+        let min = quote_option(attr.min);
+        let max = quote_option(attr.max);
+        let step = quote_option(attr.step);
+        field_widgets.push(quote! {
+            changed |= ::plugin_interfaces::pluginui::SettingsField::settings_field(
+                &mut self.#ident,
+                ui,
+                &::plugin_interfaces::pluginui::FieldOptions {
+                    label: ::std::string::String::from(#label),
+                    min: #min,
+                    max: #max,
+                    step: #step,
+                },
+            );
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::plugin_interfaces::pluginui::SettingsUi for #name #ty_generics #where_clause {
+            fn settings_ui(&mut self, ui: &mut ::plugin_interfaces::Ui) -> bool {
+                let mut changed = false;
+                #(#field_widgets)*
+                changed
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput, variants: Vec<&syn::Variant>) -> syn::Result<TokenStream2> {
+    if variants.is_empty() {
+        return Err(syn::Error::new(
+            input.span(),
+            "PluginSettings cannot be derived for enums without variants",
+        ));
+    }
+
+    let mut idents = Vec::new();
+    let mut labels = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.span(),
+                "PluginSettings can only be derived for enums with unit variants",
+            ));
+        }
+        let attr = SettingsAttr::from_attrs(&variant.attrs)?;
+        labels.push(
+            attr.label
+                .unwrap_or_else(|| humanize(&variant.ident.to_string())),
+        );
+        idents.push(&variant.ident);
+    }
+    let indices = 0..idents.len();
+    let indices_back = 0..idents.len();
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::plugin_interfaces::pluginui::SettingsField for #name #ty_generics #where_clause {
+            fn settings_field(
+                &mut self,
+                ui: &mut ::plugin_interfaces::Ui,
+                options: &::plugin_interfaces::pluginui::FieldOptions,
+            ) -> bool {
+                let labels: ::std::vec::Vec<&'static str> = ::std::vec![#(#labels),*];
+                let current: usize = match self {
+                    #(Self::#idents => #indices,)*
+                };
+                let mut selected = ::core::option::Option::Some(labels[current]);
+                ui.horizontal(|ui| {
+                    if !options.label.is_empty() {
+                        ui.label(options.label.as_str());
+                    }
+                    let response = ui.combo_box(labels.clone(), &mut selected, &options.label);
+                    let index = selected
+                        .and_then(|label| labels.iter().position(|l| *l == label))
+                        .unwrap_or(current);
+                    if index != current {
+                        *self = match index {
+                            #(#indices_back => Self::#idents,)*
+                            _ => unreachable!(),
+                        };
+                    }
+                    response.changed() && index != current
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,250 @@
+//! 插件包检查工具（`cli` 特性）
+//!
+//! ```text
+//! plugin-interfaces validate <dir>
+//! ```
+//!
+//! 检查插件目录中的 config.toml 与动态库，一次性报告所有问题。
+
+use plugin_interfaces::{
+    PluginConfig, CREATE_PLUGIN_SYMBOL, DESTROY_PLUGIN_SYMBOL, PLUGIN_ABI_VERSION_SYMBOL,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: plugin-interfaces validate <dir>";
+
+/// [plugin] 中的必填字段
+const REQUIRED_FIELDS: &[&str] = &["id", "name", "description", "version"];
+
+/// 检查结果
+#[derive(Default)]
+struct Report {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(message.into());
+    }
+
+    fn warning(&mut self, message: impl Into<String>) {
+        self.warnings.push(message.into());
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [command, dir] if command == "validate" => validate_command(Path::new(dir)),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn validate_command(dir: &Path) -> ExitCode {
+    let report = validate(dir);
+    for warning in &report.warnings {
+        println!("warning: {}", warning);
+    }
+    for error in &report.errors {
+        println!("error: {}", error);
+    }
+
+    if report.errors.is_empty() {
+        println!(
+            "{}: ok ({} warning(s))",
+            dir.display(),
+            report.warnings.len()
+        );
+        ExitCode::SUCCESS
+    } else {
+        println!(
+            "{}: {} error(s), {} warning(s)",
+            dir.display(),
+            report.errors.len(),
+            report.warnings.len()
+        );
+        ExitCode::FAILURE
+    }
+}
+
+fn validate(dir: &Path) -> Report {
+    let mut report = Report::default();
+    if !dir.is_dir() {
+        report.error(format!("{} is not a directory", dir.display()));
+        return report;
+    }
+
+    let config = validate_config(&dir.join("config.toml"), &mut report);
+    let library_path = config
+        .as_ref()
+        .and_then(|config| config.library_path.as_ref())
+        .map(|path| dir.join(path));
+    validate_libraries(dir, library_path, &mut report);
+    report
+}
+
+/// 检查 config.toml，解析成功时返回配置
+fn validate_config(path: &Path, report: &mut Report) -> Option<PluginConfig> {
+    let mut content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            report.error(format!("{}: {}", path.display(), e));
+            return None;
+        }
+    };
+
+    // 先逐项检查必填字段，PluginConfig 解析只会报告第一个缺失的字段；
+    // 缺失的字段用占位值补齐，以便继续检查其余内容
+    if let Ok(mut table) = content.parse::<toml::Table>() {
+        let plugin = table
+            .entry("plugin")
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        match plugin.as_table_mut() {
+            Some(plugin) => {
+                let mut patched = false;
+                for field in REQUIRED_FIELDS {
+                    match plugin.get(*field) {
+                        None => {
+                            report.error(format!("Missing '{}' in [plugin] section", field));
+                            let placeholder = if *field == "version" { "0.0.0" } else { "-" };
+                            plugin.insert(field.to_string(), placeholder.into());
+                            patched = true;
+                        }
+                        Some(toml::Value::String(text)) if text.trim().is_empty() => {
+                            report.error(format!("'{}' in [plugin] section is empty", field));
+                        }
+                        _ => {}
+                    }
+                }
+                if patched {
+                    content = toml::to_string(&table).unwrap_or(content);
+                }
+            }
+            None => report.error("[plugin] must be a table"),
+        }
+    }
+
+    let config = match content.parse::<PluginConfig>() {
+        Ok(config) => config,
+        Err(e) => {
+            report.error(format!("{}: {}", path.display(), e));
+            return None;
+        }
+    };
+
+    for warning in &config.warnings {
+        report.warning(warning.clone());
+    }
+
+    if let Err(e) = semver::Version::parse(&config.version) {
+        report.error(format!(
+            "version '{}' is not a valid semver version: {}",
+            config.version, e
+        ));
+    }
+
+    // 逐项校验设置，报告所有无效的设置
+    for (key, setting) in &config.settings {
+        if let Err(e) = setting.resolve(key) {
+            report.error(e.to_string());
+        }
+    }
+
+    // 不读取当前环境变量，逐项报告每个实例的无效覆盖
+    for instance_id in config.instances.keys() {
+        let (resolved, errors) = config.check_instance(instance_id);
+        for warning in resolved.warnings.iter().skip(config.warnings.len()) {
+            report.warning(warning.clone());
+        }
+        for e in errors {
+            report.error(e.to_string());
+        }
+    }
+
+    Some(config)
+}
+
+/// 检查动态库是否导出 create_plugin、destroy_plugin 与 plugin_abi_version
+fn validate_libraries(dir: &Path, library_path: Option<PathBuf>, report: &mut Report) {
+    let libraries = match library_path {
+        Some(path) if path.is_file() => vec![path],
+        Some(path) => {
+            report.error(format!("library_path {} does not exist", path.display()));
+            return;
+        }
+        None => find_libraries(dir),
+    };
+    if libraries.is_empty() {
+        report.error(format!(
+            "No shared library (.so/.dll/.dylib) found in {}",
+            dir.display()
+        ));
+        return;
+    }
+
+    for library in libraries {
+        match exported_symbols(&library) {
+            Ok(exports) => {
+                for symbol in [
+                    CREATE_PLUGIN_SYMBOL,
+                    DESTROY_PLUGIN_SYMBOL,
+                    PLUGIN_ABI_VERSION_SYMBOL,
+                ] {
+                    let symbol = String::from_utf8_lossy(symbol);
+                    if !exports.contains(symbol.as_ref()) {
+                        report.error(format!(
+                            "{} does not export '{}'",
+                            library.display(),
+                            symbol
+                        ));
+                    }
+                }
+            }
+            Err(e) => report.error(format!("{}: {}", library.display(), e)),
+        }
+    }
+}
+
+fn find_libraries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut libraries: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == "so" || ext == "dll" || ext == "dylib")
+        })
+        .collect();
+    libraries.sort();
+    libraries
+}
+
+/// 读取动态库的导出符号（不加载动态库）
+fn exported_symbols(path: &Path) -> Result<HashSet<String>, String> {
+    use object::Object;
+
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let file = object::File::parse(&*data).map_err(|e| e.to_string())?;
+    let exports = file.exports().map_err(|e| e.to_string())?;
+    Ok(exports
+        .iter()
+        .map(|export| {
+            let name = String::from_utf8_lossy(export.name());
+            // Mach-O 的符号名带有前导下划线
+            match file.format() {
+                object::BinaryFormat::MachO => name.trim_start_matches('_').to_string(),
+                _ => name.to_string(),
+            }
+        })
+        .collect())
+}
@@ -4,6 +4,10 @@ use std::sync::{Arc, Mutex, OnceLock};
 
 /// 主程序提供给插件的回调函数集合
 /// 这些函数指针在插件加载时由主程序传递给插件
+///
+/// 回调返回的非空字符串由宿主分配，插件复制后会调用 `free_string` 交还宿主释放
+///
+/// 结构体布局属于插件ABI：新增回调只能追加在末尾，并同时提升 `PLUGIN_ABI_VERSION`
 #[repr(C)]
 #[derive(Clone)]
 pub struct HostCallbacks {
@@ -15,6 +19,66 @@ pub struct HostCallbacks {
 
     /// 调用其他插件
     pub call_other_plugin: extern "C" fn(*const c_char, *const c_char) -> *const c_char,
+
+    /// 读取持久化存储：(实例ID, 键) -> JSON 值，不存在时返回空指针
+    pub storage_get: extern "C" fn(*const c_char, *const c_char) -> *const c_char,
+
+    /// 写入持久化存储：(实例ID, 键, JSON 值) -> 是否成功
+    pub storage_set: extern "C" fn(*const c_char, *const c_char, *const c_char) -> bool,
+
+    /// 删除持久化存储中的键：(实例ID, 键) -> 是否成功
+    pub storage_delete: extern "C" fn(*const c_char, *const c_char) -> bool,
+
+    /// 列出持久化存储中的键：(实例ID) -> JSON 字符串数组
+    pub storage_list: extern "C" fn(*const c_char) -> *const c_char,
+
+    /// 释放宿主回调返回的字符串
+    pub free_string: extern "C" fn(*mut c_char),
+
+    /// 订阅应用配置项变更：(实例ID, 键) -> 是否成功
+    /// 配置项变更时宿主调用插件的 `on_app_config_changed`
+    pub subscribe_app_config: extern "C" fn(*const c_char, *const c_char) -> bool,
+
+    /// 取消订阅应用配置项变更：(实例ID, 键) -> 是否成功
+    pub unsubscribe_app_config: extern "C" fn(*const c_char, *const c_char) -> bool,
+
+    /// 读取密钥：(插件ID, 键) -> 明文，不存在时返回空指针
+    /// 宿主应将密钥保存在系统密钥链等安全位置，而不是配置文件中
+    pub secret_get: extern "C" fn(*const c_char, *const c_char) -> *const c_char,
+
+    /// 保存密钥：(插件ID, 键, 明文) -> 是否成功
+    pub secret_set: extern "C" fn(*const c_char, *const c_char, *const c_char) -> bool,
+
+    /// 删除密钥：(插件ID, 键) -> 是否成功
+    pub secret_delete: extern "C" fn(*const c_char, *const c_char) -> bool,
+}
+
+impl HostCallbacks {
+    /// 使用 `CString::from_raw` 作为 `free_string`
+    /// 适用于通过 `CString::into_raw` 返回字符串的 Rust 宿主
+    pub fn with_cstring_free(mut self) -> Self {
+        self.free_string = free_cstring;
+        self
+    }
+
+    /// 复制宿主返回的字符串并交还宿主释放
+    pub(crate) fn take_string(&self, ptr: *const c_char) -> Option<String> {
+        if ptr.is_null() {
+            return None;
+        }
+        let text = unsafe { std::ffi::CStr::from_ptr(ptr) }
+            .to_str()
+            .ok()
+            .map(|s| s.to_string());
+        (self.free_string)(ptr as *mut c_char);
+        text
+    }
+}
+
+extern "C" fn free_cstring(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        let _ = unsafe { std::ffi::CString::from_raw(ptr) };
+    }
 }
 
 impl std::fmt::Debug for HostCallbacks {
@@ -23,6 +87,16 @@ impl std::fmt::Debug for HostCallbacks {
             .field("send_to_frontend", &"<function pointer>")
             .field("get_app_config", &"<function pointer>")
             .field("call_other_plugin", &"<function pointer>")
+            .field("storage_get", &"<function pointer>")
+            .field("storage_set", &"<function pointer>")
+            .field("storage_delete", &"<function pointer>")
+            .field("storage_list", &"<function pointer>")
+            .field("free_string", &"<function pointer>")
+            .field("subscribe_app_config", &"<function pointer>")
+            .field("unsubscribe_app_config", &"<function pointer>")
+            .field("secret_get", &"<function pointer>")
+            .field("secret_set", &"<function pointer>")
+            .field("secret_delete", &"<function pointer>")
             .finish()
     }
 }
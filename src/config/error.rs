@@ -0,0 +1,103 @@
+use super::SettingsError;
+use std::path::PathBuf;
+
+/// 配置加载错误
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// 配置文件不存在
+    MissingFile { path: PathBuf },
+    /// 配置文件无法读取
+    Io { path: PathBuf, message: String },
+    /// TOML 语法或结构错误，行列号从 1 开始
+    Parse {
+        message: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+    /// 缺少必需的配置段
+    MissingSection { section: String },
+    /// 缺少必填字段
+    MissingField { section: String, field: String },
+    /// 实例覆盖或环境变量的值无效；source 为 `[instances.<id>]` 或环境变量名
+    InvalidOverride {
+        source: String,
+        key: String,
+        message: String,
+    },
+    /// 设置项取值不符合声明
+    InvalidSetting(SettingsError),
+}
+
+impl ConfigError {
+    /// 从 TOML 解析错误构造，并根据源文本计算行列号
+    pub(crate) fn from_toml(error: &toml::de::Error, source: &str) -> Self {
+        let (line, column) = match error.span() {
+            Some(span) => {
+                let (line, column) = line_column(source, span.start);
+                (Some(line), Some(column))
+            }
+            None => (None, None),
+        };
+        ConfigError::Parse {
+            message: error.message().trim().to_string(),
+            line,
+            column,
+        }
+    }
+}
+
+/// 将字节偏移转换为行列号（从 1 开始）
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before, |newline| &before[newline + 1..])
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::MissingFile { path } => {
+                write!(f, "Config file not found: {}", path.display())
+            }
+            ConfigError::Io { path, message } => {
+                write!(f, "Failed to read {}: {}", path.display(), message)
+            }
+            ConfigError::Parse {
+                message,
+                line: Some(line),
+                column: Some(column),
+            } => write!(
+                f,
+                "Parse error at line {}, column {}: {}",
+                line, column, message
+            ),
+            ConfigError::Parse { message, .. } => write!(f, "Parse error: {}", message),
+            ConfigError::MissingSection { section } => {
+                write!(f, "Missing [{}] section", section)
+            }
+            ConfigError::MissingField { section, field } => {
+                write!(f, "Missing '{}' in [{}] section", field, section)
+            }
+            ConfigError::InvalidOverride {
+                source,
+                key,
+                message,
+            } => write!(f, "Invalid override '{}' in {}: {}", key, source, message),
+            ConfigError::InvalidSetting(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<SettingsError> for ConfigError {
+    fn from(error: SettingsError) -> Self {
+        ConfigError::InvalidSetting(error)
+    }
+}
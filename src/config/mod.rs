@@ -0,0 +1,262 @@
+mod error;
+mod overrides;
+mod settings;
+mod watcher;
+
+use crate::metadata::PluginMetadata;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub use error::ConfigError;
+pub use settings::{PluginSettings, SettingSchema, SettingType, SettingsError, SettingsSchema};
+pub use watcher::{ConfigChange, ConfigWatcher};
+
+/// 插件配置结构
+///
+/// ```toml
+/// [plugin]
+/// id = "chat"
+/// name = "Chat"
+/// description = "Chat plugin"
+/// version = "0.1.0"
+/// author = "someone"          # 可选
+/// disabled = false            # 可选，默认 false
+/// require_history = true      # 可选，默认 false
+/// library_path = "libchat.so" # 可选
+///
+/// [settings.temperature]      # 可选，见 SettingSchema
+/// type = "float"
+/// default = 0.7
+///
+/// [instances.work]            # 可选，按实例覆盖，见 resolve_for_instance
+/// name = "Chat (work)"
+///
+/// [openai]                    # 其他顶层表作为扩展配置保留，与固定表名相近的会产生警告
+/// endpoint = "https://..."
+/// ```
+#[derive(Debug, Clone)]
+pub struct PluginConfig {
+    pub id: String,
+    pub disabled: bool,
+    pub name: String,
+    pub description: String,
+    pub version: String,
+    pub author: Option<String>,
+    pub library_path: Option<String>,              // 动态库文件路径
+    pub require_history: bool,                     // 是否需要接收历史记录
+    pub config_path: String,                       // 配置文件路径，由 from_path 填充
+    pub settings: SettingsSchema,                  // [settings] 中声明的设置项
+    pub instances: BTreeMap<String, toml::Table>,  // [instances.<id>] 按实例覆盖
+    pub extensions: BTreeMap<String, toml::Value>, // 其他顶层表（插件自定义配置）
+    pub warnings: Vec<String>,                     // 解析时发现的未知键与疑似拼写错误的表
+}
+
+/// config.toml 的原始结构
+#[derive(Deserialize)]
+struct RawConfig {
+    plugin: Option<RawPlugin>,
+    #[serde(default)]
+    settings: SettingsSchema,
+    #[serde(default)]
+    instances: BTreeMap<String, toml::Table>,
+    #[serde(flatten)]
+    extensions: BTreeMap<String, toml::Value>,
+}
+
+/// [plugin] 段的原始结构，必填字段在转换时检查以便给出 MissingField 错误
+#[derive(Deserialize)]
+struct RawPlugin {
+    id: Option<String>,
+    #[serde(default)]
+    disabled: bool,
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<String>,
+    author: Option<String>,
+    library_path: Option<String>,
+    #[serde(default)]
+    require_history: bool,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+impl PluginConfig {
+    /// 从当前目录下的config.toml文件读取配置
+    /// 宿主加载插件时应使用 `from_path(&metadata.config_path)`
+    pub fn from_file() -> Result<Self, ConfigError> {
+        Self::from_path("config.toml")
+    }
+
+    /// 从指定路径读取配置
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let config_content = fs::read_to_string(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ConfigError::MissingFile {
+                path: path.to_path_buf(),
+            },
+            _ => ConfigError::Io {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            },
+        })?;
+        let mut config: Self = config_content.parse()?;
+        config.config_path = path.to_string_lossy().to_string();
+        Ok(config)
+    }
+
+    /// 校验设置项并应用默认值
+    pub fn settings(&self) -> Result<PluginSettings, SettingsError> {
+        PluginSettings::from_schema(self.settings.clone())
+    }
+
+    /// 读取扩展配置表，例如 `config.extension::<OpenAiConfig>("openai")`
+    pub fn extension<T: serde::de::DeserializeOwned>(&self, name: &str) -> Option<T> {
+        self.extensions
+            .get(name)
+            .and_then(|value| value.clone().try_into().ok())
+    }
+
+    /// 转换为插件实例的元数据
+    /// 多实例时应先调用 `resolve_for_instance` 应用实例覆盖
+    pub fn into_metadata(self, instance_id: impl Into<String>) -> PluginMetadata {
+        PluginMetadata {
+            id: self.id,
+            disabled: self.disabled,
+            name: self.name,
+            description: self.description,
+            version: self.version,
+            author: self.author,
+            library_path: self.library_path,
+            config_path: self.config_path,
+            instance_id: Some(instance_id.into()),
+            require_history: self.require_history,
+        }
+    }
+}
+
+/// 从TOML文本解析配置，例如 `content.parse::<PluginConfig>()`
+impl std::str::FromStr for PluginConfig {
+    type Err = ConfigError;
+
+    fn from_str(config_content: &str) -> Result<Self, Self::Err> {
+        let raw: RawConfig = toml::from_str(config_content)
+            .map_err(|e| ConfigError::from_toml(&e, config_content))?;
+
+        let plugin = raw.plugin.ok_or_else(|| ConfigError::MissingSection {
+            section: "plugin".to_string(),
+        })?;
+
+        let mut warnings: Vec<String> = plugin
+            .unknown
+            .keys()
+            .map(|key| format!("Unknown key '{}' in [plugin] section", key))
+            .collect();
+        warnings.extend(extension_warnings(&raw.extensions));
+
+        Ok(PluginConfig {
+            id: required(plugin.id, "id")?,
+            disabled: plugin.disabled,
+            name: required(plugin.name, "name")?,
+            description: required(plugin.description, "description")?,
+            version: required(plugin.version, "version")?,
+            author: plugin.author,
+            library_path: plugin.library_path,
+            require_history: plugin.require_history,
+            config_path: String::new(),
+            settings: raw.settings,
+            instances: raw.instances,
+            extensions: raw.extensions,
+            warnings,
+        })
+    }
+}
+
+/// config.toml 中有固定含义的顶层表
+const SECTIONS: &[&str] = &["plugin", "settings", "instances"];
+
+/// 检查扩展配置：与固定表名相近的表多半是拼写错误（例如 `[setting]`、`[instance.work]`），
+/// 顶层的非表键多半是放错位置的 [plugin] 字段
+fn extension_warnings(extensions: &BTreeMap<String, toml::Value>) -> Vec<String> {
+    extensions
+        .iter()
+        .filter_map(|(name, value)| {
+            let lowercase = name.to_lowercase();
+            if let Some(section) = SECTIONS
+                .iter()
+                .find(|section| edit_distance(&lowercase, section) <= 2)
+            {
+                Some(format!(
+                    "Unknown section [{}], did you mean [{}]?",
+                    name, section
+                ))
+            } else if !value.is_table() {
+                Some(format!(
+                    "Top-level key '{}' is not a table; extension config must be a [table]",
+                    name
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// 两个字符串之间的编辑距离
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// 检查 [plugin] 中的必填字段
+fn required(value: Option<String>, field: &str) -> Result<String, ConfigError> {
+    value.ok_or_else(|| ConfigError::MissingField {
+        section: "plugin".to_string(),
+        field: field.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLUGIN: &str =
+        "[plugin]\nid = \"chat\"\nname = \"Chat\"\ndescription = \"Chat\"\nversion = \"0.1.0\"\n";
+
+    #[test]
+    fn misspelled_sections_are_reported() {
+        let content = format!(
+            "{}\n[setting.temperature]\ntype = \"float\"\n\n[instance.work]\nname = \"Work\"\n\n[openai]\nendpoint = \"x\"\n",
+            PLUGIN
+        );
+        let config: PluginConfig = content.parse().unwrap();
+        assert_eq!(
+            config.warnings,
+            vec![
+                "Unknown section [instance], did you mean [instances]?".to_string(),
+                "Unknown section [setting], did you mean [settings]?".to_string(),
+            ]
+        );
+        assert!(config.extensions.contains_key("openai"));
+    }
+
+    #[test]
+    fn top_level_values_are_reported() {
+        let content = format!("author = \"me\"\n{}", PLUGIN);
+        let config: PluginConfig = content.parse().unwrap();
+        assert_eq!(
+            config.warnings,
+            vec!["Top-level key 'author' is not a table; extension config must be a [table]"]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use super::{ConfigError, PluginConfig, SettingType};
+use serde_json::Value;
+
+/// 可以在 `[instances.<id>]` 中覆盖的 [plugin] 字段
+const OVERRIDABLE_FIELDS: &[&str] = &[
+    "disabled",
+    "name",
+    "description",
+    "author",
+    "require_history",
+];
+
+impl PluginConfig {
+    /// 解析某个实例实际使用的配置，优先级从低到高：
+    ///
+    /// 1. 配置文件中的 [plugin] 与 [settings]
+    /// 2. 同一文件中的 `[instances.<instance_id>]` 覆盖
+    /// 3. 环境变量 `PLUGIN_<ID>_<KEY>`（见 [`PluginConfig::env_var_name`]）
+    ///
+    /// ```toml
+    /// [instances.work]
+    /// name = "Chat (work)"
+    ///
+    /// [instances.work.settings]
+    /// temperature = 0.2
+    /// ```
+    pub fn resolve_for_instance(&self, instance_id: &str) -> Result<PluginConfig, ConfigError> {
+        self.resolve_with(instance_id, |name| std::env::var(name).ok())
+    }
+
+    /// 设置项对应的环境变量名，例如插件 `my-chat` 的 `max_tokens` 为 `PLUGIN_MY_CHAT_MAX_TOKENS`
+    pub fn env_var_name(&self, key: &str) -> String {
+        format!("PLUGIN_{}_{}", env_segment(&self.id), env_segment(key))
+    }
+
+    /// 检查某个实例的覆盖，报告所有无效的覆盖而不是只报告第一个
+    ///
+    /// 不读取环境变量，结果只取决于配置文件本身，适合打包前的校验。
+    /// 返回应用了有效覆盖后的配置以及所有错误。
+    pub fn check_instance(&self, instance_id: &str) -> (PluginConfig, Vec<ConfigError>) {
+        self.resolve_all(instance_id, |_| None)
+    }
+
+    fn resolve_with(
+        &self,
+        instance_id: &str,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<PluginConfig, ConfigError> {
+        let (config, errors) = self.resolve_all(instance_id, env);
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(config),
+        }
+    }
+
+    /// 应用所有有效的覆盖，并收集每个无效覆盖的错误
+    fn resolve_all(
+        &self,
+        instance_id: &str,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> (PluginConfig, Vec<ConfigError>) {
+        let mut config = self.clone();
+        let mut errors = Vec::new();
+        if let Some(overrides) = self.instances.get(instance_id) {
+            config.apply_instance_overrides(instance_id, overrides, &mut errors);
+        }
+        config.apply_env_overrides(env, &mut errors);
+        // 解析后的配置只描述一个实例
+        config.instances.clear();
+        (config, errors)
+    }
+
+    fn apply_instance_overrides(
+        &mut self,
+        instance_id: &str,
+        overrides: &toml::Table,
+        errors: &mut Vec<ConfigError>,
+    ) {
+        let source = format!("[instances.{}]", instance_id);
+        for (key, value) in overrides {
+            let invalid = |message: &str| ConfigError::InvalidOverride {
+                source: source.clone(),
+                key: key.clone(),
+                message: message.to_string(),
+            };
+            match key.as_str() {
+                "disabled" => match value.as_bool() {
+                    Some(disabled) => self.disabled = disabled,
+                    None => errors.push(invalid("expected a boolean")),
+                },
+                "require_history" => match value.as_bool() {
+                    Some(require_history) => self.require_history = require_history,
+                    None => errors.push(invalid("expected a boolean")),
+                },
+                "name" | "description" | "author" => {
+                    let Some(text) = value.as_str() else {
+                        errors.push(invalid("expected a string"));
+                        continue;
+                    };
+                    let text = text.to_string();
+                    match key.as_str() {
+                        "name" => self.name = text,
+                        "description" => self.description = text,
+                        _ => self.author = Some(text),
+                    }
+                }
+                "settings" => {
+                    let Some(values) = value.as_table() else {
+                        errors.push(invalid("expected a table of setting values"));
+                        continue;
+                    };
+                    for (setting_key, setting_value) in values {
+                        let result = serde_json::to_value(setting_value)
+                            .map_err(|e| ConfigError::InvalidOverride {
+                                source: source.clone(),
+                                key: setting_key.clone(),
+                                message: e.to_string(),
+                            })
+                            .and_then(|value| self.override_setting(&source, setting_key, value));
+                        if let Err(e) = result {
+                            errors.push(e);
+                        }
+                    }
+                }
+                _ => self.warnings.push(format!(
+                    "Unknown key '{}' in {}; only {} and settings can be overridden",
+                    key,
+                    source,
+                    OVERRIDABLE_FIELDS.join(", ")
+                )),
+            }
+        }
+    }
+
+    fn apply_env_overrides(
+        &mut self,
+        env: impl Fn(&str) -> Option<String>,
+        errors: &mut Vec<ConfigError>,
+    ) {
+        let keys: Vec<(String, SettingType)> = self
+            .settings
+            .iter()
+            .map(|(key, schema)| (key.clone(), schema.kind))
+            .collect();
+        for (key, kind) in keys {
+            let name = self.env_var_name(&key);
+            let Some(raw) = env(&name) else {
+                continue;
+            };
+            let result = parse_env_value(kind, &raw)
+                .ok_or_else(|| ConfigError::InvalidOverride {
+                    source: name.clone(),
+                    key: key.clone(),
+                    message: format!("cannot parse {:?} as {:?}", raw, kind),
+                })
+                .and_then(|value| self.override_setting(&name, &key, value));
+            if let Err(e) = result {
+                errors.push(e);
+            }
+        }
+    }
+
+    /// 覆盖设置项的当前值，并按声明校验
+    fn override_setting(
+        &mut self,
+        source: &str,
+        key: &str,
+        value: Value,
+    ) -> Result<(), ConfigError> {
+        let schema = self
+            .settings
+            .get_mut(key)
+            .ok_or_else(|| ConfigError::InvalidOverride {
+                source: source.to_string(),
+                key: key.to_string(),
+                message: "not declared in [settings]".to_string(),
+            })?;
+        let value = schema
+            .validate(key, &value)
+            .map_err(|e| ConfigError::InvalidOverride {
+                source: source.to_string(),
+                key: key.to_string(),
+                message: e.message,
+            })?;
+        schema.value = Some(value);
+        Ok(())
+    }
+}
+
+/// 转换为环境变量名片段：大写，非字母数字字符替换为下划线
+fn env_segment(text: &str) -> String {
+    text.chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// 按设置项类型解析环境变量的值
+fn parse_env_value(kind: SettingType, raw: &str) -> Option<Value> {
+    let raw = raw.trim();
+    match kind {
+        SettingType::String => Some(Value::String(raw.to_string())),
+        SettingType::Bool => raw.parse::<bool>().ok().map(Value::from),
+        SettingType::Integer => raw.parse::<i64>().ok().map(Value::from),
+        SettingType::Float => raw.parse::<f64>().ok().map(Value::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "[plugin]\nid = \"chat\"\nname = \"Chat\"\ndescription = \"Chat\"\nversion = \"0.1.0\"\n\n[settings.temperature]\ntype = \"float\"\ndefault = 0.7\nmin = 0.0\nmax = 2.0\n\n[settings.model]\ntype = \"string\"\ndefault = \"small\"\n\n[settings.stream]\ntype = \"bool\"\ndefault = false\n\n[instances.work.settings]\ntemperature = 0.2\nmodel = \"large\"\n";
+
+    fn setting(config: &PluginConfig, key: &str) -> Value {
+        config.settings().unwrap().get(key).cloned().unwrap()
+    }
+
+    #[test]
+    fn env_overrides_instance_overrides_file() {
+        let config: PluginConfig = CONFIG.parse().unwrap();
+        let resolved = config
+            .resolve_with("work", |name| {
+                (name == "PLUGIN_CHAT_TEMPERATURE").then(|| "1.5".to_string())
+            })
+            .unwrap();
+        assert_eq!(setting(&resolved, "temperature"), Value::from(1.5));
+        assert_eq!(setting(&resolved, "model"), Value::from("large"));
+        assert_eq!(setting(&resolved, "stream"), Value::from(false));
+
+        let other = config.resolve_with("home", |_| None).unwrap();
+        assert_eq!(setting(&other, "temperature"), Value::from(0.7));
+        assert_eq!(setting(&other, "model"), Value::from("small"));
+    }
+
+    #[test]
+    fn check_instance_reports_every_invalid_override() {
+        let content = format!(
+            "{}\n[instances.home]\ndisabled = \"no\"\nname = \"Home\"\n\n[instances.home.settings]\ntemperature = 5.0\nstream = \"yes\"\nmodel = \"tiny\"\n",
+            CONFIG
+        );
+        let config: PluginConfig = content.parse().unwrap();
+        let (resolved, errors) = config.check_instance("home");
+        let keys: Vec<&str> = errors
+            .iter()
+            .filter_map(|error| match error {
+                ConfigError::InvalidOverride { key, .. } => Some(key.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(keys, ["disabled", "stream", "temperature"]);
+        // Valid overrides are still applied
+        assert_eq!(resolved.name, "Home");
+        assert_eq!(setting(&resolved, "model"), Value::from("tiny"));
+    }
+
+    #[test]
+    fn invalid_override_keeps_its_source() {
+        let config: PluginConfig = CONFIG.parse().unwrap();
+        let error = config
+            .resolve_with("home", |name| {
+                (name == "PLUGIN_CHAT_TEMPERATURE").then(|| "3.0".to_string())
+            })
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ConfigError::InvalidOverride { ref source, ref key, .. }
+                if source == "PLUGIN_CHAT_TEMPERATURE" && key == "temperature"
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// 设置项类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingType {
+    String,
+    Bool,
+    Integer,
+    Float,
+}
+
+/// 单个设置项的声明，对应 config.toml 中的 `[settings.<key>]`
+///
+/// ```toml
+/// [settings.temperature]
+/// type = "float"
+/// default = 0.7
+/// min = 0.0
+/// max = 2.0
+/// description = "Sampling temperature"
+///
+/// [settings.model]
+/// type = "string"
+/// default = "gpt-4o"
+/// enum = ["gpt-4o", "claude"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SettingSchema {
+    #[serde(rename = "type")]
+    pub kind: SettingType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 允许的取值列表
+    #[serde(default, rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// 当前值，未设置时使用 default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+/// 全部设置项声明（键 -> 声明）
+pub type SettingsSchema = BTreeMap<String, SettingSchema>;
+
+impl SettingSchema {
+    /// 校验取值是否符合声明，返回规范化后的值（例如整数写法的浮点数转为浮点数）
+    pub fn validate(&self, key: &str, value: &Value) -> Result<Value, SettingsError> {
+        let value = match self.kind {
+            SettingType::String if value.is_string() => value.clone(),
+            SettingType::Bool if value.is_boolean() => value.clone(),
+            SettingType::Integer if value.is_i64() || value.is_u64() => value.clone(),
+            SettingType::Float if value.is_number() => {
+                Value::from(value.as_f64().unwrap_or_default())
+            }
+            _ => {
+                return Err(SettingsError::new(
+                    key,
+                    format!("expected {}, found {}", self.kind.name(), value),
+                ))
+            }
+        };
+
+        if let Some(allowed) = &self.enum_values {
+            if !allowed
+                .iter()
+                .any(|candidate| same_value(candidate, &value))
+            {
+                return Err(SettingsError::new(
+                    key,
+                    format!("{} is not one of {}", value, Value::from(allowed.clone())),
+                ));
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            if let Some(min) = self.min.filter(|min| number < *min) {
+                return Err(SettingsError::new(
+                    key,
+                    format!("{} is below minimum {}", number, min),
+                ));
+            }
+            if let Some(max) = self.max.filter(|max| number > *max) {
+                return Err(SettingsError::new(
+                    key,
+                    format!("{} is above maximum {}", number, max),
+                ));
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// 计算当前生效的值：优先使用 value，其次 default；两者都未设置时返回 None
+    pub fn resolve(&self, key: &str) -> Result<Option<Value>, SettingsError> {
+        if let Some(default) = &self.default {
+            self.validate(key, default)
+                .map_err(|e| SettingsError::new(key, format!("invalid default: {}", e.message)))?;
+        }
+        self.value
+            .as_ref()
+            .or(self.default.as_ref())
+            .map(|value| self.validate(key, value))
+            .transpose()
+    }
+}
+
+impl SettingType {
+    fn name(&self) -> &'static str {
+        match self {
+            SettingType::String => "string",
+            SettingType::Bool => "bool",
+            SettingType::Integer => "integer",
+            SettingType::Float => "float",
+        }
+    }
+}
+
+/// 数值按大小比较，其余按相等比较（使 `enum = [1, 2]` 也能匹配浮点设置）
+fn same_value(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// 设置项校验错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsError {
+    pub key: String,
+    pub message: String,
+}
+
+impl SettingsError {
+    pub fn new(key: &str, message: impl Into<String>) -> Self {
+        Self {
+            key: key.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid setting '{}': {}", self.key, self.message)
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+/// 经过校验、已应用默认值的插件设置
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PluginSettings {
+    schema: SettingsSchema,
+    values: BTreeMap<String, Value>,
+}
+
+impl PluginSettings {
+    /// 根据设置声明解析当前值并校验
+    pub fn from_schema(schema: SettingsSchema) -> Result<Self, SettingsError> {
+        let mut values = BTreeMap::new();
+        for (key, setting) in &schema {
+            if let Some(value) = setting.resolve(key)? {
+                values.insert(key.clone(), value);
+            }
+        }
+        Ok(Self { schema, values })
+    }
+
+    /// 获取原始值
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(Value::as_bool)
+    }
+
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get(key).and_then(Value::as_i64)
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(Value::as_f64)
+    }
+
+    /// 将值反序列化为任意类型（例如插件自定义的枚举）
+    pub fn get_as<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// 修改设置值，按声明校验；未声明的键会被拒绝
+    pub fn set(&mut self, key: &str, value: Value) -> Result<(), SettingsError> {
+        let setting = self
+            .schema
+            .get(key)
+            .ok_or_else(|| SettingsError::new(key, "not declared in [settings]"))?;
+        let value = setting.validate(key, &value)?;
+        self.values.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// 获取设置项声明（用于生成设置界面或描述）
+    pub fn schema(&self, key: &str) -> Option<&SettingSchema> {
+        self.schema.get(key)
+    }
+
+    /// 所有已声明的设置项
+    pub fn schemas(&self) -> &SettingsSchema {
+        &self.schema
+    }
+
+    /// 所有当前值
+    pub fn values(&self) -> &BTreeMap<String, Value> {
+        &self.values
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schema.is_empty()
+    }
+}
@@ -0,0 +1,140 @@
+use super::{ConfigError, PluginConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 配置文件变更
+#[derive(Debug, Clone)]
+pub struct ConfigChange {
+    /// 变更前的配置文件内容
+    pub old_content: String,
+    /// 变更后的配置文件内容
+    pub new_content: String,
+    /// 变更后解析得到的配置
+    pub config: PluginConfig,
+}
+
+/// 供宿主使用的配置文件监视器（轮询修改时间，无需额外依赖）
+///
+/// ```ignore
+/// let mut watcher = ConfigWatcher::new(&metadata.config_path);
+/// // 在宿主的事件循环中定期调用
+/// match watcher.poll() {
+///     Ok(Some(change)) => unsafe { interface.call_on_config_changed(&change) },
+///     Ok(None) => 0,
+///     Err(e) => { eprintln!("{}", e); 0 }
+/// };
+/// ```
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    content: Option<String>,
+}
+
+impl ConfigWatcher {
+    /// 创建监视器并记录当前文件内容作为基准
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let modified = modified_time(&path);
+        let content = fs::read_to_string(&path).ok();
+        Self {
+            path,
+            modified,
+            content,
+        }
+    }
+
+    /// 被监视的配置文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 检查配置文件是否变更
+    ///
+    /// 文件内容变化且能成功解析时返回 `Some(change)` 并更新基准；
+    /// 解析失败时返回错误并保留基准，之后文件再次修改前不会重复报告，
+    /// 修复后会与上一次有效内容比较。
+    pub fn poll(&mut self) -> Result<Option<ConfigChange>, ConfigError> {
+        let modified = modified_time(&self.path);
+        if modified.is_some() && modified == self.modified {
+            return Ok(None);
+        }
+
+        let new_content = fs::read_to_string(&self.path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ConfigError::MissingFile {
+                path: self.path.clone(),
+            },
+            _ => ConfigError::Io {
+                path: self.path.clone(),
+                message: e.to_string(),
+            },
+        })?;
+        if self.content.as_deref() == Some(new_content.as_str()) {
+            self.modified = modified;
+            return Ok(None);
+        }
+
+        // 无效内容也记录修改时间，同一次错误的修改只报告一次
+        self.modified = modified;
+        let mut config = new_content.parse::<PluginConfig>()?;
+        config.config_path = self.path.to_string_lossy().to_string();
+
+        let old_content = self
+            .content
+            .replace(new_content.clone())
+            .unwrap_or_default();
+        Ok(Some(ConfigChange {
+            old_content,
+            new_content,
+            config,
+        }))
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const CONFIG: &str =
+        "[plugin]\nid = \"chat\"\nname = \"Chat\"\ndescription = \"Chat\"\nversion = \"0.1.0\"\n";
+
+    /// 写入内容并设置修改时间，避免依赖文件系统的时间精度
+    fn write(path: &Path, content: &str, seconds: u64) {
+        fs::write(path, content).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+            .unwrap();
+    }
+
+    #[test]
+    fn broken_revision_is_reported_once() {
+        let path = std::env::temp_dir().join(format!(
+            "plugin-interfaces-watcher-{}.toml",
+            std::process::id()
+        ));
+        write(&path, CONFIG, 1_000);
+        let mut watcher = ConfigWatcher::new(&path);
+        assert!(watcher.poll().unwrap().is_none());
+
+        write(&path, "[plugin", 2_000);
+        assert!(watcher.poll().is_err());
+        assert!(watcher.poll().unwrap().is_none());
+
+        let fixed = CONFIG.replace("Chat\"\ndescription", "Chat 2\"\ndescription");
+        write(&path, &fixed, 3_000);
+        let change = watcher.poll().unwrap().unwrap();
+        assert_eq!(change.old_content, CONFIG);
+        assert_eq!(change.config.name, "Chat 2");
+
+        let _ = fs::remove_file(path);
+    }
+}
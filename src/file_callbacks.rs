@@ -0,0 +1,215 @@
+//! 文件存储与文件密钥回调的公共部分
+//!
+//! 包括全局存储的注册、回调参数的读取、原子写入文件，以及返回字符串的释放。
+//!
+//! 文件回调返回的字符串由本模块分配并登记。`free_string` 被替换为
+//! `free_file_string`：登记过的字符串由本模块释放，其余字符串仍交给宿主原来的
+//! `free_string`，因此宿主其他回调可以继续使用自己的分配方式。
+//!
+//! 宿主原来的 `free_string` 只记录一份，进程内所有回调集合共用。
+//! 因此同一进程中使用文件存储或文件密钥的各个回调集合必须使用同一个宿主释放函数，
+//! 后注册的不同释放函数会替换先前的（并记录警告）。
+
+use crate::callbacks::HostCallbacks;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{c_char, CStr, CString};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use zeroize::Zeroizing;
+
+/// 通过 `install` 注册、供文件回调使用的存储，按实例ID或插件ID区分
+///
+/// 回调只能拿到 ID 参数，因此存储放在进程级的表中；
+/// 每个 ID 各自注册，互不影响，同一 ID 再次注册时替换原来的存储。
+pub(crate) struct Installed<T>(Mutex<Option<HashMap<String, T>>>);
+
+impl<T> Installed<T> {
+    pub(crate) const fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    pub(crate) fn install(&self, id: &str, value: T) {
+        if let Ok(mut installed) = self.0.lock() {
+            installed
+                .get_or_insert_with(HashMap::new)
+                .insert(id.to_string(), value);
+        }
+    }
+
+    pub(crate) fn with<R>(&self, id: &str, f: impl FnOnce(&T) -> Option<R>) -> Option<R> {
+        let installed = self.0.lock().ok()?;
+        f(installed.as_ref()?.get(id)?)
+    }
+}
+
+/// 读取回调的字符串参数
+///
+/// # Safety
+/// `ptr` 为空或指向在返回值使用期间有效的 C 字符串
+pub(crate) unsafe fn str_arg<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        None
+    } else {
+        CStr::from_ptr(ptr).to_str().ok()
+    }
+}
+
+/// 写入文件：先写临时文件再重命名，避免写入中断导致数据损坏
+/// `private` 为 true 时文件仅当前用户可读写（Unix 下权限为 0600）
+pub(crate) fn write_atomic(path: &Path, data: &[u8], private: bool) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(&temp_path)?.write_all(data)?;
+    fs::rename(temp_path, path)
+}
+
+/// 宿主原来的 `free_string`
+static HOST_FREE_STRING: OnceLock<Mutex<Option<extern "C" fn(*mut c_char)>>> = OnceLock::new();
+
+/// 文件回调返回、尚未释放的字符串
+static FILE_STRINGS: OnceLock<Mutex<HashSet<usize>>> = OnceLock::new();
+
+fn file_strings() -> &'static Mutex<HashSet<usize>> {
+    FILE_STRINGS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// 将 `free_string` 换成 `free_file_string`，并记住宿主原来的释放函数
+///
+/// 宿主的释放函数全进程只有一份，要求所有回调集合使用同一个宿主分配器
+pub(crate) fn chain_free_string(mut callbacks: HostCallbacks) -> HostCallbacks {
+    let free_string = callbacks.free_string;
+    // 多次调用时不能把自己记为宿主的释放函数
+    if !std::ptr::fn_addr_eq(free_string, free_file_string as extern "C" fn(*mut c_char)) {
+        let host_free = HOST_FREE_STRING.get_or_init(|| Mutex::new(None));
+        if let Ok(mut host_free) = host_free.lock() {
+            if host_free.is_some_and(|previous| !std::ptr::fn_addr_eq(previous, free_string)) {
+                crate::log_warn!(
+                    "free_string differs from the one registered earlier; strings from the earlier callbacks will be freed with the new one"
+                );
+            }
+            *host_free = Some(free_string);
+        }
+    }
+    callbacks.free_string = free_file_string;
+    callbacks
+}
+
+/// 返回给插件的字符串，插件复制后通过 `free_string` 释放（释放前清零）
+pub(crate) fn return_string(text: &str) -> *const c_char {
+    // 一次分配好结尾的 nul，避免扩容时留下未清零的旧缓冲区
+    let mut bytes = Vec::with_capacity(text.len() + 1);
+    bytes.extend_from_slice(text.as_bytes());
+    let Ok(text) = CString::new(bytes) else {
+        return std::ptr::null();
+    };
+    let ptr = text.into_raw();
+    match file_strings().lock() {
+        Ok(mut strings) => {
+            strings.insert(ptr as usize);
+            ptr
+        }
+        Err(_) => {
+            // 无法登记时不能交出，否则释放时会被当作宿主的字符串
+            drop(Zeroizing::new(
+                unsafe { CString::from_raw(ptr) }.into_bytes_with_nul(),
+            ));
+            std::ptr::null()
+        }
+    }
+}
+
+extern "C" fn free_file_string(ptr: *mut c_char) {
+    if ptr.is_null() {
+        return;
+    }
+    let owned = file_strings()
+        .lock()
+        .is_ok_and(|mut strings| strings.remove(&(ptr as usize)));
+    if owned {
+        drop(Zeroizing::new(
+            unsafe { CString::from_raw(ptr) }.into_bytes_with_nul(),
+        ));
+        return;
+    }
+    let host_free = HOST_FREE_STRING
+        .get()
+        .and_then(|host_free| host_free.lock().ok().and_then(|host_free| *host_free));
+    if let Some(host_free) = host_free {
+        host_free(ptr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static HOST_FREED: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn host_free(ptr: *mut c_char) {
+        HOST_FREED.store(ptr as usize, Ordering::SeqCst);
+    }
+
+    extern "C" fn send(_: *const c_char, _: *const c_char) -> bool {
+        false
+    }
+
+    extern "C" fn get(_: *const c_char) -> *const c_char {
+        std::ptr::null()
+    }
+
+    extern "C" fn get2(_: *const c_char, _: *const c_char) -> *const c_char {
+        std::ptr::null()
+    }
+
+    extern "C" fn set(_: *const c_char, _: *const c_char, _: *const c_char) -> bool {
+        false
+    }
+
+    #[test]
+    fn file_strings_do_not_reach_host_free() {
+        let callbacks = HostCallbacks {
+            send_to_frontend: send,
+            get_app_config: get,
+            call_other_plugin: get2,
+            storage_get: get2,
+            storage_set: set,
+            storage_delete: send,
+            storage_list: get,
+            free_string: host_free,
+            subscribe_app_config: send,
+            unsubscribe_app_config: send,
+            secret_get: get2,
+            secret_set: set,
+            secret_delete: send,
+        };
+        let callbacks = chain_free_string(chain_free_string(callbacks));
+
+        let file_string = return_string("value");
+        (callbacks.free_string)(file_string as *mut c_char);
+        assert_eq!(HOST_FREED.load(Ordering::SeqCst), 0);
+
+        let mut host_string = *b"host\0";
+        (callbacks.free_string)(host_string.as_mut_ptr() as *mut c_char);
+        assert_eq!(
+            HOST_FREED.load(Ordering::SeqCst),
+            host_string.as_ptr() as usize
+        );
+    }
+}
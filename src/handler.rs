@@ -1,7 +1,8 @@
 use crate::callbacks::HostCallbacks;
-use crate::log_info;
+use crate::config::{ConfigError, PluginConfig};
 use crate::metadata::{PluginInstanceContext, PluginMetadata};
-use crate::pluginui::{Context, Ui};
+use crate::pluginui::{Context, CreationContext, Ui};
+use crate::{log_info, log_warn};
 
 /// 插件处理器 trait
 /// 定义了插件的生命周期方法，使用上下文传递模式
@@ -22,9 +23,32 @@ pub trait PluginHandler: Send + Sync {
         let mut context = PluginInstanceContext::new(instance_id, metadata);
         context.set_callbacks(callbacks);
 
+        // 从 config_path 加载配置，应用实例与环境变量覆盖后校验 [settings]
+        // 配置无效时初始化失败，避免插件以空设置静默运行
+        if !context.metadata.config_path.is_empty() {
+            let config = PluginConfig::from_path(&context.metadata.config_path)
+                .and_then(|config| config.resolve_for_instance(&context.instance_id))?;
+            for warning in &config.warnings {
+                log_warn!("{}", warning);
+            }
+            context
+                .apply_config(&config)
+                .map_err(ConfigError::InvalidSetting)?;
+        }
+
         Ok(context)
     }
 
+    /// 初始化UI（在挂载之后、首次 update_ui 之前调用一次）
+    /// 可通过创建上下文读取插件配置，准备UI所需的初始状态
+    fn init_ui(
+        &mut self,
+        _ctx: &CreationContext,
+        _ui: &mut Ui,
+        _plugin_ctx: &PluginInstanceContext,
+    ) {
+    }
+
     /// 更新UI（事件驱动）
     /// 当前端用户交互或需要更新UI时调用
     fn update_ui(&mut self, ctx: &Context, ui: &mut Ui, plugin_ctx: &PluginInstanceContext);
@@ -46,6 +70,55 @@ pub trait PluginHandler: Send + Sync {
         Ok(())
     }
 
+    /// 配置文件变更时调用（宿主检测到 config.toml 被修改后通知）
+    /// 调用前上下文中的设置与元数据已更新为新配置
+    fn on_config_changed(
+        &mut self,
+        _old: &PluginConfig,
+        new: &PluginConfig,
+        plugin_ctx: &PluginInstanceContext,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        log_info!(
+            "Plugin config changed. id={}, version={}, instance_id={}",
+            new.id,
+            new.version,
+            plugin_ctx.get_instance_id()
+        );
+        Ok(())
+    }
+
+    /// 已订阅的应用配置项变更时调用（见 `PluginInstanceContext::subscribe_app_config`）
+    /// value 为新值，配置项被删除时为 None
+    fn on_app_config_changed(
+        &mut self,
+        key: &str,
+        _value: Option<&str>,
+        plugin_ctx: &PluginInstanceContext,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        log_info!(
+            "App config changed. key={}, instance_id={}",
+            key,
+            plugin_ctx.get_instance_id()
+        );
+        Ok(())
+    }
+
+    /// 保存插件状态（插件库重新加载前调用），返回空数据表示无需保存
+    /// 状态格式由插件自行决定，例如 `serde_json::to_vec(&self.state)`
+    fn save_state(&mut self, _plugin_ctx: &PluginInstanceContext) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// 恢复插件状态（新版本插件重新初始化后调用），数据来自旧版本的 save_state
+    /// 新版本应能处理旧格式的数据，无法识别时返回错误即可
+    fn restore_state(
+        &mut self,
+        _state: &[u8],
+        _plugin_ctx: &PluginInstanceContext,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
     /// 插件卸载时调用
     fn on_dispose(
         &mut self,
@@ -2,11 +2,14 @@
 pub mod api;
 pub mod callbacks;
 pub mod config;
+mod file_callbacks;
 pub mod handler;
 pub mod logging;
 pub mod message;
 pub mod metadata;
 pub mod pluginui;
+pub mod secrets;
+pub mod storage;
 pub mod symbols;
 
 // 重新导出所有公共接口
@@ -18,7 +21,13 @@ pub use logging::*;
 pub use message::*;
 pub use metadata::*;
 pub use pluginui::{Context, CreationContext, PluginUiOption, Ui};
+pub use secrets::*;
+pub use storage::*;
 pub use symbols::*;
 
+// 派生宏：从配置结构体生成设置界面
+#[cfg(feature = "derive")]
+pub use plugin_interfaces_derive::PluginSettings;
+
 // 导出插件实例上下文
 pub use metadata::PluginInstanceContext;
@@ -1,2 +1,3 @@
+#[allow(clippy::module_inception)]
 mod logging;
 pub use logging::*;
@@ -1,8 +1,10 @@
+use crate::config::{PluginConfig, PluginSettings, SettingsError};
 use crate::log_error;
 use crate::message::{
     PluginStreamMessage, StreamControlData, StreamDataData, StreamEndData, StreamError, StreamInfo,
     StreamMessageData, StreamStartData, StreamStatus, STREAM_MANAGER,
 };
+use crate::secrets::Secret;
 use serde::{Deserialize, Serialize};
 use std::os::raw::c_char;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -85,6 +87,9 @@ impl PluginMetadata {
 
 /// 释放FFI元数据结构中的字符串内存
 /// 必须在不再使用PluginMetadataFFI时调用
+///
+/// # Safety
+/// 所有非空指针必须由 `PluginMetadata::to_ffi` 分配，且只能释放一次
 pub unsafe fn free_plugin_metadata_ffi(metadata: PluginMetadataFFI) {
     use std::ffi::CString;
 
@@ -134,6 +139,7 @@ pub struct PluginInstanceContext {
     pub metadata: PluginMetadata,
     pub callbacks: Option<crate::callbacks::HostCallbacks>,
     pub history: Option<Vec<HistoryMessage>>, // 当前会话的历史记录
+    pub settings: PluginSettings,             // 经过校验的插件设置
 }
 
 impl PluginInstanceContext {
@@ -144,6 +150,7 @@ impl PluginInstanceContext {
             metadata,
             callbacks: None,
             history: None,
+            settings: PluginSettings::default(),
         }
     }
 
@@ -162,6 +169,30 @@ impl PluginInstanceContext {
         &self.metadata
     }
 
+    /// 获取插件设置（config.toml 中 `[settings]` 声明的设置项）
+    pub fn settings(&self) -> &PluginSettings {
+        &self.settings
+    }
+
+    /// 设置插件设置
+    pub fn set_settings(&mut self, settings: PluginSettings) {
+        self.settings = settings;
+    }
+
+    /// 应用新的配置：更新元数据中来自配置文件的字段并重新校验设置
+    /// 设置校验失败时不做任何修改
+    pub fn apply_config(&mut self, config: &PluginConfig) -> Result<(), SettingsError> {
+        let settings = config.settings()?;
+        self.settings = settings;
+        self.metadata.disabled = config.disabled;
+        self.metadata.name = config.name.clone();
+        self.metadata.description = config.description.clone();
+        self.metadata.version = config.version.clone();
+        self.metadata.author = config.author.clone();
+        self.metadata.require_history = config.require_history;
+        Ok(())
+    }
+
     /// 获取回调函数
     pub fn get_callbacks(&self) -> Option<&crate::callbacks::HostCallbacks> {
         self.callbacks.as_ref()
@@ -207,23 +238,105 @@ impl PluginInstanceContext {
             use std::ffi::CString;
             if let Ok(key_str) = CString::new(key) {
                 let result_ptr = (callbacks.get_app_config)(key_str.as_ptr());
-                if !result_ptr.is_null() {
-                    unsafe {
-                        let c_str = std::ffi::CStr::from_ptr(result_ptr);
-                        return c_str.to_str().ok().map(|s| s.to_string());
-                    }
-                }
+                return callbacks.take_string(result_ptr);
             }
         }
         None
     }
 
+    /// 获取应用配置并反序列化为指定类型
+    /// 值按 JSON 解析；不是合法 JSON 时按普通字符串处理
+    pub fn get_app_config_as<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let raw = self.get_app_config(key)?;
+        let value = serde_json::from_str(&raw).unwrap_or(serde_json::Value::String(raw));
+        serde_json::from_value(value).ok()
+    }
+
+    /// 订阅应用配置项变更，变更时调用 `PluginHandler::on_app_config_changed`
+    pub fn subscribe_app_config(&self, key: &str) -> bool {
+        if let Some(callbacks) = &self.callbacks {
+            use std::ffi::CString;
+            if let (Ok(id_str), Ok(key_str)) =
+                (CString::new(self.instance_id.as_str()), CString::new(key))
+            {
+                return (callbacks.subscribe_app_config)(id_str.as_ptr(), key_str.as_ptr());
+            }
+        }
+        false
+    }
+
+    /// 取消订阅应用配置项变更
+    pub fn unsubscribe_app_config(&self, key: &str) -> bool {
+        if let Some(callbacks) = &self.callbacks {
+            use std::ffi::CString;
+            if let (Ok(id_str), Ok(key_str)) =
+                (CString::new(self.instance_id.as_str()), CString::new(key))
+            {
+                return (callbacks.unsubscribe_app_config)(id_str.as_ptr(), key_str.as_ptr());
+            }
+        }
+        false
+    }
+
+    /// 获取本实例的持久化存储
+    pub fn storage(&self) -> crate::storage::Storage<'_> {
+        crate::storage::Storage::new(&self.instance_id, self.callbacks.as_ref())
+    }
+
+    /// 读取密钥（例如 API Key），密钥按插件ID保存，同一插件的所有实例共享
+    pub fn get_secret(&self, key: &str) -> Option<Secret> {
+        if let Some(callbacks) = &self.callbacks {
+            use std::ffi::CString;
+            if let (Ok(id_str), Ok(key_str)) =
+                (CString::new(self.metadata.id.as_str()), CString::new(key))
+            {
+                let result_ptr = (callbacks.secret_get)(id_str.as_ptr(), key_str.as_ptr());
+                return callbacks.take_string(result_ptr).map(Secret::new);
+            }
+        }
+        None
+    }
+
+    /// 保存密钥，传给宿主的明文副本与 `value` 在调用后清零
+    pub fn set_secret(&self, key: &str, value: Secret) -> bool {
+        if let Some(callbacks) = &self.callbacks {
+            use std::ffi::CString;
+            if let (Ok(id_str), Ok(key_str), Ok(value_str)) = (
+                CString::new(self.metadata.id.as_str()),
+                CString::new(key),
+                CString::new(value.expose()),
+            ) {
+                let value_str = zeroize::Zeroizing::new(value_str.into_bytes_with_nul());
+                return (callbacks.secret_set)(
+                    id_str.as_ptr(),
+                    key_str.as_ptr(),
+                    value_str.as_ptr() as *const std::ffi::c_char,
+                );
+            }
+        }
+        false
+    }
+
+    /// 删除密钥
+    pub fn delete_secret(&self, key: &str) -> bool {
+        if let Some(callbacks) = &self.callbacks {
+            use std::ffi::CString;
+            if let (Ok(id_str), Ok(key_str)) =
+                (CString::new(self.metadata.id.as_str()), CString::new(key))
+            {
+                return (callbacks.secret_delete)(id_str.as_ptr(), key_str.as_ptr());
+            }
+        }
+        false
+    }
+
     /// 调用其他插件
     pub fn call_other_plugin(&self, plugin_id: &str, message: &str) -> Option<String> {
         if let Some(callbacks) = &self.callbacks {
             use std::ffi::CString;
             if let (Ok(id_str), Ok(msg_str)) = (CString::new(plugin_id), CString::new(message)) {
                 let result_ptr = (callbacks.call_other_plugin)(id_str.as_ptr(), msg_str.as_ptr());
+                // 该回调返回的字符串仍归宿主所有，插件只复制、不通过 `free_string` 释放
                 if !result_ptr.is_null() {
                     unsafe {
                         let c_str = std::ffi::CStr::from_ptr(result_ptr);
@@ -293,6 +406,36 @@ impl PluginInstanceContext {
         self.send_to_frontend("plugin-ui-refreshed", &payload)
     }
 
+    /// 请求前端弹出确认对话框
+    /// 返回对话框ID；用户的选择会作为该ID的UI事件（"true"/"false"）送回，
+    /// 可在 update_ui 中通过 `Ui::confirm_result` 读取
+    pub fn confirm(&self, title: &str, message: &str) -> Option<String> {
+        let plugin_id = &self.metadata.id;
+        let instance_id = self
+            .metadata
+            .instance_id
+            .as_ref()
+            .unwrap_or(&self.metadata.id);
+        let dialog_id = format!("dialog_{}", uuid::Uuid::new_v4());
+
+        // 构建对话框请求事件的载荷
+        let payload = serde_json::json!({
+            "plugin": plugin_id,
+            "instance": instance_id,
+            "dialog_id": dialog_id,
+            "kind": "confirm",
+            "title": title,
+            "message": message,
+        })
+        .to_string();
+
+        if self.send_to_frontend("plugin-dialog-request", &payload) {
+            Some(dialog_id)
+        } else {
+            None
+        }
+    }
+
     /// 请求前端断开连接
     pub fn call_disconnect(&self) -> bool {
         let plugin_id = &self.metadata.id;
@@ -354,6 +497,9 @@ impl PluginInstanceContext {
 }
 
 /// 将 FFI 元数据转换为 Rust 元数据
+///
+/// # Safety
+/// 所有非空指针必须指向有效的、以 NUL 结尾的 C 字符串
 pub unsafe fn convert_ffi_to_metadata(metadata_ffi: PluginMetadataFFI) -> PluginMetadata {
     use std::ffi::CStr;
 
@@ -437,7 +583,7 @@ pub unsafe fn convert_ffi_to_metadata(metadata_ffi: PluginMetadataFFI) -> Plugin
         library_path,
         config_path,
         instance_id,
-        require_history: metadata_ffi.require_history, // FFI 转换时默认为 false，实际值从配置文件读取
+        require_history: metadata_ffi.require_history, // 宿主通过 PluginConfig::into_metadata 从配置文件读取
     }
 }
 
@@ -7,6 +7,14 @@ pub struct Response {
     pub clicked: bool,
     pub changed: bool,
     pub hovered: bool,
+    /// The widget currently has keyboard focus
+    pub has_focus: bool,
+    /// The widget received keyboard focus this frame
+    pub gained_focus: bool,
+    /// The widget lost keyboard focus this frame
+    pub lost_focus: bool,
+    /// Enter was pressed while the widget had focus this frame
+    pub enter_pressed: bool,
     /// Component ID for event handling
     pub component_id: Option<String>,
 }
@@ -17,6 +25,10 @@ impl Response {
             clicked: false,
             changed: false,
             hovered: false,
+            has_focus: false,
+            gained_focus: false,
+            lost_focus: false,
+            enter_pressed: false,
             component_id: None,
         }
     }
@@ -29,8 +41,8 @@ impl Response {
         Self {
             clicked,
             changed,
-            hovered: false,
             component_id: Some(component_id),
+            ..Self::new()
         }
     }
 
@@ -46,30 +58,90 @@ impl Response {
         self.hovered
     }
 
+    pub fn has_focus(&self) -> bool {
+        self.has_focus
+    }
+
+    pub fn gained_focus(&self) -> bool {
+        self.gained_focus
+    }
+
+    /// Whether the widget lost focus this frame, e.g. to commit a text field on blur
+    pub fn lost_focus(&self) -> bool {
+        self.lost_focus
+    }
+
+    pub fn enter_pressed(&self) -> bool {
+        self.enter_pressed
+    }
+
     pub fn with_clicked() -> Self {
         Self {
             clicked: true,
-            changed: false,
-            hovered: false,
-            component_id: None,
+            ..Self::new()
         }
     }
 
     pub fn with_changed() -> Self {
         Self {
-            clicked: false,
             changed: true,
-            hovered: false,
-            component_id: None,
+            ..Self::new()
         }
     }
 }
 
+impl Default for Response {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Response of a container widget together with the value returned by its contents
+pub struct InnerResponse<R> {
+    pub inner: R,
+    pub response: Response,
+}
+
+impl<R> InnerResponse<R> {
+    pub fn new(inner: R, response: Response) -> Self {
+        Self { inner, response }
+    }
+}
+
 /// Internal component representation for serialization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UiComponent {
     pub id: String,
     pub component: UiComponentType,
+    /// Disabled components are rendered greyed out and ignore user input
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Hidden components are kept in the tree but not rendered
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    /// Validation message shown next to the component
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Style hints for the frontend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<Style>,
+}
+
+impl UiComponent {
+    pub fn new(id: String, component: UiComponentType) -> Self {
+        Self {
+            id,
+            component,
+            enabled: true,
+            visible: true,
+            error: None,
+            style: None,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +150,25 @@ pub enum UiComponentType {
     Label {
         text: String,
     },
+    RichText {
+        text: String,
+        bold: bool,
+        italic: bool,
+        monospace: bool,
+        color: Option<Color>,
+    },
+    Hyperlink {
+        url: String,
+        text: String,
+    },
+    Markdown {
+        text: String,
+    },
+    Image {
+        source: ImageSource,
+        /// Width and height in pixels; the frontend uses the natural size when absent
+        size: Option<[f32; 2]>,
+    },
     Button {
         text: String,
         enabled: bool,
@@ -98,6 +189,77 @@ pub enum UiComponentType {
     Toggle {
         value: bool,
     },
+    Checkbox {
+        value: bool,
+        label: String,
+    },
+    RadioButton {
+        checked: bool,
+        label: String,
+    },
+    Slider {
+        value: f64,
+        min: f64,
+        max: f64,
+        step: Option<f64>,
+    },
+    DragValue {
+        value: f64,
+        min: Option<f64>,
+        max: Option<f64>,
+        step: f64,
+    },
+    Table {
+        columns: Vec<TableColumn>,
+        /// One `TableRow` component per row
+        rows: Vec<UiComponent>,
+        selected_row: Option<usize>,
+        sort: Option<TableSort>,
+    },
+    TableRow {
+        cells: Vec<UiComponent>,
+    },
+    Collapsing {
+        title: String,
+        open: bool,
+        children: Vec<UiComponent>,
+    },
+    Tabs {
+        tabs: Vec<String>,
+        selected: usize,
+        /// Contents of the selected tab only
+        children: Vec<UiComponent>,
+    },
+    ScrollArea {
+        max_height: f32,
+        children: Vec<UiComponent>,
+    },
+    Separator,
+    Form {
+        submit_text: String,
+        /// Whether every field currently passes validation
+        valid: bool,
+        children: Vec<UiComponent>,
+    },
+    FormField {
+        label: String,
+        required: bool,
+        children: Vec<UiComponent>,
+    },
+    Modal {
+        title: String,
+        children: Vec<UiComponent>,
+    },
+    ProgressBar {
+        /// Completion between 0.0 and 1.0
+        fraction: f32,
+        text: Option<String>,
+    },
+    Spinner,
+    Badge {
+        text: String,
+        color: Color,
+    },
     Horizontal {
         children: Vec<UiComponent>,
     },
@@ -105,3 +267,260 @@ pub enum UiComponentType {
         children: Vec<UiComponent>,
     },
 }
+
+/// Color used by colored widgets
+///
+/// Semantic colors are resolved by the frontend according to the current theme.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Color {
+    Default,
+    Primary,
+    Success,
+    Warning,
+    Error,
+    Info,
+    /// CSS hex color such as `#ff8800`
+    Hex(String),
+}
+
+/// Horizontal alignment hint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Style hints attached to a component; the frontend may ignore any of them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Style {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+    /// Width in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub align: Option<Align>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+/// Styled text for labels
+///
+/// ```ignore
+/// ui.label(RichText::new("Failed").bold().color(Color::Error));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RichText {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub monospace: bool,
+    pub color: Option<Color>,
+}
+
+impl RichText {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub fn monospace(mut self) -> Self {
+        self.monospace = true;
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Whether any styling is applied on top of the plain text
+    pub fn is_plain(&self) -> bool {
+        !self.bold && !self.italic && !self.monospace && self.color.is_none()
+    }
+}
+
+impl From<&str> for RichText {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for RichText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&String> for RichText {
+    fn from(text: &String) -> Self {
+        Self::new(text.as_str())
+    }
+}
+
+/// Where an image is loaded from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageSource {
+    /// Local file path, read by the host
+    Path(String),
+    /// `data:` URI with inline image data
+    DataUri(String),
+}
+
+impl From<&str> for ImageSource {
+    fn from(source: &str) -> Self {
+        if source.starts_with("data:") {
+            ImageSource::DataUri(source.to_string())
+        } else {
+            ImageSource::Path(source.to_string())
+        }
+    }
+}
+
+impl From<String> for ImageSource {
+    fn from(source: String) -> Self {
+        ImageSource::from(source.as_str())
+    }
+}
+
+/// Column header of a table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableColumn {
+    pub title: String,
+    /// Whether clicking the header requests sorting by this column
+    pub sortable: bool,
+}
+
+impl TableColumn {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            sortable: false,
+        }
+    }
+
+    pub fn sortable(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            sortable: true,
+        }
+    }
+}
+
+/// Requested sort order of a table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSort {
+    pub column: usize,
+    pub ascending: bool,
+}
+
+/// Plugin-owned table state, updated from frontend events
+///
+/// The plugin is responsible for actually ordering its rows according to `sort`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableState {
+    pub selected_row: Option<usize>,
+    pub sort: Option<TableSort>,
+}
+
+/// Event sent by the frontend for a table component
+///
+/// A `Select { index }` event selects a row; otherwise the event value is a string:
+/// - `select:<row>` selects a row
+/// - `sort:<column>` sorts by a column, toggling the direction if it is already sorted
+/// - `sort:<column>:asc` / `sort:<column>:desc` sorts in an explicit direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableEvent {
+    SelectRow(usize),
+    Sort {
+        column: usize,
+        ascending: Option<bool>,
+    },
+}
+
+impl TableEvent {
+    /// Parse the event value sent by the frontend; a bare number selects that row
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Ok(row) = value.parse::<usize>() {
+            return Some(TableEvent::SelectRow(row));
+        }
+        let mut parts = value.split(':');
+        match parts.next()? {
+            "select" => parts.next()?.parse().ok().map(TableEvent::SelectRow),
+            "sort" => {
+                let column = parts.next()?.parse().ok()?;
+                let ascending = match parts.next() {
+                    None => None,
+                    Some("asc") => Some(true),
+                    Some("desc") => Some(false),
+                    Some(_) => return None,
+                };
+                Some(TableEvent::Sort { column, ascending })
+            }
+            _ => None,
+        }
+    }
+
+    /// Read a typed event value: a number selects a row, a string is parsed as above
+    pub fn from_value(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::Number(number) => number
+                .as_u64()
+                .map(|row| TableEvent::SelectRow(row as usize)),
+            serde_json::Value::String(text) => Self::parse(text),
+            _ => None,
+        }
+    }
+
+    /// Apply the event to a table state, returning whether anything changed
+    pub fn apply(&self, state: &mut TableState) -> bool {
+        let previous = state.clone();
+        match *self {
+            TableEvent::SelectRow(row) => state.selected_row = Some(row),
+            TableEvent::Sort { column, ascending } => {
+                let ascending = ascending.unwrap_or(match state.sort {
+                    Some(sort) if sort.column == column => !sort.ascending,
+                    _ => true,
+                });
+                state.sort = Some(TableSort { column, ascending });
+            }
+        }
+        *state != previous
+    }
+}
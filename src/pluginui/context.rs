@@ -1,6 +1,10 @@
 //! Context types for the plugin UI framework
 
+use crate::metadata::PluginMetadata;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 /// Creation context passed to plugins during initialization
 #[derive(Debug)]
@@ -22,6 +26,39 @@ impl<'a> CreationContext<'a> {
     pub fn with_config(plugin_id: &'a str, config: HashMap<String, String>) -> Self {
         Self { config, plugin_id }
     }
+
+    /// Create a creation context populated from plugin metadata
+    ///
+    /// `config` contains `id`, `name`, `description`, `version`, `config_path`
+    /// and `require_history`, plus `author`, `library_path` and `instance_id`
+    /// when set.
+    pub fn from_metadata(metadata: &'a PluginMetadata) -> Self {
+        let mut config = HashMap::new();
+        config.insert("id".to_string(), metadata.id.clone());
+        config.insert("name".to_string(), metadata.name.clone());
+        config.insert("description".to_string(), metadata.description.clone());
+        config.insert("version".to_string(), metadata.version.clone());
+        config.insert("config_path".to_string(), metadata.config_path.clone());
+        config.insert(
+            "require_history".to_string(),
+            metadata.require_history.to_string(),
+        );
+        if let Some(author) = &metadata.author {
+            config.insert("author".to_string(), author.clone());
+        }
+        if let Some(library_path) = &metadata.library_path {
+            config.insert("library_path".to_string(), library_path.clone());
+        }
+        if let Some(instance_id) = &metadata.instance_id {
+            config.insert("instance_id".to_string(), instance_id.clone());
+        }
+        Self::with_config(&metadata.id, config)
+    }
+
+    /// Get a configuration value
+    pub fn get_config(&self, key: &str) -> Option<&String> {
+        self.config.get(key)
+    }
 }
 
 /// Runtime context for UI operations
@@ -35,16 +72,15 @@ pub struct Context {
     pub ui_state: UiState,
     /// UI event data from frontend
     pub ui_event_data: HashMap<String, String>,
+    /// When this context was created; `ui_state.time` is measured from here
+    created_at: Instant,
+    /// Shortest repaint delay requested during the current frame
+    repaint_after: Cell<Option<Duration>>,
 }
 
 impl Context {
     pub fn new(plugin_id: String) -> Self {
-        Self {
-            plugin_id,
-            theme: Theme::default(),
-            ui_state: UiState::default(),
-            ui_event_data: HashMap::new(),
-        }
+        Self::with_ui_event_data(plugin_id, HashMap::new())
     }
 
     /// Create context with UI event data
@@ -54,30 +90,111 @@ impl Context {
             theme: Theme::default(),
             ui_state: UiState::default(),
             ui_event_data,
+            created_at: Instant::now(),
+            repaint_after: Cell::new(None),
         }
     }
 
+    /// Replace the UI event data for the next frame
+    ///
+    /// Hosts should keep one `Context` per plugin instance and update it,
+    /// rather than creating a new one per event, so frame timing carries over.
+    pub fn set_ui_event_data(&mut self, ui_event_data: HashMap<String, String>) {
+        self.ui_event_data = ui_event_data;
+    }
+
+    /// Advance the frame counter and clock (called by the host before `update_ui`)
+    pub fn begin_frame(&mut self) {
+        let now = self.created_at.elapsed().as_secs_f64();
+        self.ui_state.delta_time = if self.ui_state.frame_count == 0 {
+            0.0
+        } else {
+            now - self.ui_state.time
+        };
+        self.ui_state.frame_count += 1;
+        self.ui_state.time = now;
+        self.repaint_after.set(None);
+    }
+
+    /// Ask the host to call `update_ui` again after `duration`
+    ///
+    /// If called several times in one frame, the shortest delay wins.
+    pub fn request_repaint_after(&self, duration: Duration) {
+        let shortest = match self.repaint_after.get() {
+            Some(current) => current.min(duration),
+            None => duration,
+        };
+        self.repaint_after.set(Some(shortest));
+    }
+
+    /// Ask the host to call `update_ui` again as soon as possible
+    pub fn request_repaint(&self) {
+        self.request_repaint_after(Duration::ZERO);
+    }
+
+    /// Repaint delay requested by the plugin during the last frame (read by the host)
+    pub fn repaint_after(&self) -> Option<Duration> {
+        self.repaint_after.get()
+    }
+
+    /// Set the host theme (builder style)
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     /// Get UI event data for a specific component
+    ///
+    /// This is the raw string passed by the host. Typed events (`UiEvent`) are
+    /// delivered to `Ui`; read them through widget responses, e.g. `Ui::table`
+    /// applies row selection and sort requests to its `TableState`.
     pub fn get_ui_event_data(&self, component_id: &str) -> Option<&String> {
         self.ui_event_data.get(component_id)
     }
 }
 
-/// Theme information
-#[derive(Debug, Clone)]
+/// Theme information provided by the host
+///
+/// Hosts usually receive it from the frontend as JSON, e.g.
+/// `{"is_dark": true, "accent_color": "#4f46e5", "font_scale": 1.25, "locale": "zh-CN"}`;
+/// missing fields fall back to the defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Theme {
     pub is_dark: bool,
+    /// Accent color as a CSS color string
+    pub accent_color: Option<String>,
+    /// Font size multiplier, 1.0 being the default size
+    pub font_scale: f32,
+    /// BCP 47 language tag of the user interface, e.g. `en-US`
+    pub locale: String,
+}
+
+impl Theme {
+    /// Parse theme data sent by the host
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
 }
 
 impl Default for Theme {
     fn default() -> Self {
-        Self { is_dark: false }
+        Self {
+            is_dark: false,
+            accent_color: None,
+            font_scale: 1.0,
+            locale: "en-US".to_string(),
+        }
     }
 }
 
-/// UI state information
+/// UI state information, updated by `Context::begin_frame`
 #[derive(Debug, Default)]
 pub struct UiState {
+    /// Number of frames so far, starting at 1 for the first `update_ui`
     pub frame_count: u64,
+    /// Seconds since the context was created
     pub time: f64,
+    /// Seconds since the previous frame (0.0 on the first frame)
+    pub delta_time: f64,
 }
@@ -0,0 +1,101 @@
+//! Typed UI events sent by the frontend
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Event sent by the frontend for a UI component
+///
+/// Serialized with a `type` tag, e.g. `{"type": "change", "value": 0.7}`
+/// or `{"type": "select", "index": 2}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UiEvent {
+    /// The component was clicked
+    Click,
+    /// The component's value changed; the value keeps its JSON type
+    Change { value: Value },
+    /// Enter was pressed in the component, or a form was submitted
+    Submit,
+    /// An option, tab or table row was selected
+    Select { index: usize },
+    /// The pointer entered the component
+    Hover,
+    /// The pointer left the component
+    Leave,
+    /// The component gained keyboard focus
+    Focus,
+    /// The component lost keyboard focus
+    Blur,
+}
+
+impl UiEvent {
+    /// The value carried by the event, if any
+    pub fn value(&self) -> Option<Value> {
+        match self {
+            UiEvent::Change { value } => Some(value.clone()),
+            UiEvent::Select { index } => Some(Value::from(*index)),
+            _ => None,
+        }
+    }
+}
+
+/// A UI event addressed to a component, as exchanged over FFI
+///
+/// ```json
+/// {"component_id": "slider_3", "type": "change", "value": 0.7}
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiEventMessage {
+    pub component_id: String,
+    #[serde(flatten)]
+    pub event: UiEvent,
+}
+
+impl UiEventMessage {
+    pub fn new(component_id: &str, event: UiEvent) -> Self {
+        Self {
+            component_id: component_id.to_string(),
+            event,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Read an event value as text; non-string values use their JSON representation
+pub(crate) fn value_as_string(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Read an event value as a number, accepting numeric strings from older frontends
+pub(crate) fn value_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Read an event value as a boolean, accepting "true"/"false" strings
+pub(crate) fn value_as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(flag) => Some(*flag),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Read an event value as an index, accepting numeric strings
+pub(crate) fn value_as_index(value: &Value) -> Option<usize> {
+    match value {
+        Value::Number(number) => number.as_u64().map(|index| index as usize),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
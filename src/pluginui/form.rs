@@ -0,0 +1,369 @@
+//! Form builder with field validation
+
+use super::components::{Response, UiComponent, UiComponentType};
+use super::ui::{LayoutContext, Ui};
+#[cfg(feature = "regex")]
+use regex::Regex;
+use uuid::Uuid;
+
+/// Validation rule for a form field
+#[derive(Debug, Clone)]
+pub enum Validator {
+    /// The value must not be empty or whitespace only
+    Required,
+    /// The value must match the regular expression (`regex` feature)
+    #[cfg(feature = "regex")]
+    Regex { regex: Regex, message: String },
+    /// The value must be a number within the (inclusive) bounds
+    Range { min: Option<f64>, max: Option<f64> },
+}
+
+impl Validator {
+    /// Create a regex validator that reports `message` when the value does not match
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str, message: &str) -> Result<Self, regex::Error> {
+        Ok(Validator::Regex {
+            regex: Regex::new(pattern)?,
+            message: message.to_string(),
+        })
+    }
+
+    /// Create a numeric range validator
+    pub fn range(min: Option<f64>, max: Option<f64>) -> Self {
+        Validator::Range { min, max }
+    }
+
+    /// Check a value, returning the error message if it is invalid
+    ///
+    /// Apart from `Required`, validators accept empty values, so optional
+    /// fields can be left blank.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            Validator::Required => {
+                if value.trim().is_empty() {
+                    Err("This field is required".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            #[cfg(feature = "regex")]
+            Validator::Regex { regex, message } => {
+                if value.is_empty() || regex.is_match(value) {
+                    Ok(())
+                } else {
+                    Err(message.clone())
+                }
+            }
+            Validator::Range { min, max } => {
+                if value.trim().is_empty() {
+                    return Ok(());
+                }
+                let number = value
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| "Must be a number".to_string())?;
+                match (min, max) {
+                    (Some(min), Some(max)) if number < *min || number > *max => {
+                        Err(format!("Must be between {} and {}", min, max))
+                    }
+                    (Some(min), _) if number < *min => Err(format!("Must be at least {}", min)),
+                    (_, Some(max)) if number > *max => Err(format!("Must be at most {}", max)),
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Builder for the fields of a form, passed to the closure of [`Ui::form`]
+pub struct Form<'a> {
+    ui: &'a mut Ui,
+    submit_clicked: bool,
+    show_errors: bool,
+    valid: bool,
+}
+
+impl Form<'_> {
+    /// Add a labeled single-line text field
+    pub fn text_field(
+        &mut self,
+        label: &str,
+        value: &mut String,
+        validators: &[Validator],
+    ) -> Response {
+        self.field(label, validators, |ui| {
+            let response = ui.text_edit_singleline(value);
+            (response, value.clone())
+        })
+    }
+
+    /// Add a labeled numeric field
+    pub fn number_field(
+        &mut self,
+        label: &str,
+        value: &mut f64,
+        validators: &[Validator],
+    ) -> Response {
+        self.field(label, validators, |ui| {
+            let response = ui.drag_value(value, None, 0.0);
+            (response, value.to_string())
+        })
+    }
+
+    /// Access the underlying `Ui` to add other widgets to the form
+    pub fn ui(&mut self) -> &mut Ui {
+        self.ui
+    }
+
+    /// Whether all fields added so far pass validation
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    /// Whether the user submitted the form and all fields added so far are valid
+    pub fn submitted(&self) -> bool {
+        self.submit_clicked && self.valid
+    }
+
+    /// Add a field built by `add_input`, validate its value and attach the error text
+    fn field(
+        &mut self,
+        label: &str,
+        validators: &[Validator],
+        add_input: impl FnOnce(&mut Ui) -> (Response, String),
+    ) -> Response {
+        self.ui.layout_stack.push(LayoutContext::FormField);
+        let start_index = self.ui.components.len();
+
+        let (response, value) = add_input(self.ui);
+
+        let mut children = self.ui.components.split_off(start_index);
+        self.ui.layout_stack.pop();
+
+        let field_id = response.component_id.clone().unwrap_or_default();
+        if response.changed() {
+            self.ui.touched_fields.insert(field_id.clone());
+        }
+
+        let error = validators
+            .iter()
+            .find_map(|validator| validator.validate(&value).err());
+        if error.is_some() {
+            self.valid = false;
+        }
+
+        // Only nag about fields the user has edited, or once they tried to submit
+        if self.show_errors || self.ui.touched_fields.contains(&field_id) {
+            if let Some(input) = children.iter_mut().find(|child| child.id == field_id) {
+                input.error = error;
+            }
+        }
+
+        let component = UiComponent::new(
+            format!("formfield_{}", Uuid::new_v4()),
+            UiComponentType::FormField {
+                label: label.to_string(),
+                required: validators
+                    .iter()
+                    .any(|validator| matches!(validator, Validator::Required)),
+                children,
+            },
+        );
+        self.ui.add_component(component);
+
+        response
+    }
+}
+
+/// Result of building a form
+pub struct FormResponse {
+    pub response: Response,
+    pub valid: bool,
+}
+
+impl FormResponse {
+    /// Whether the user submitted the form and every field is valid
+    pub fn submitted(&self) -> bool {
+        self.response.clicked() && self.valid
+    }
+
+    /// Whether every field currently passes validation
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+}
+
+impl Ui {
+    /// Create a form with labeled, validated fields and a submit button
+    ///
+    /// Validators run every frame; error text is attached to a field once the
+    /// user has edited it or tried to submit the form. The form is submitted by
+    /// the submit button or by pressing Enter in one of its fields, and
+    /// `submitted()` is only true when every field is valid.
+    ///
+    /// `id` must be unique among the forms of the enclosing container: the
+    /// submit attempt is remembered across frames, so it cannot depend on the
+    /// widget counter. Fields get IDs under the form's, e.g. `form_login/textedit_0`.
+    pub fn form(
+        &mut self,
+        id: &str,
+        submit_text: &str,
+        add_fields: impl FnOnce(&mut Form),
+    ) -> FormResponse {
+        let id = self.scoped_id(&format!("form_{}", id));
+
+        // Enter in any of the form's fields submits it like the submit button.
+        // It is looked up before the fields are built so that every field,
+        // including those before the one Enter was pressed in, shows its error.
+        let field_prefix = format!("{}/", id);
+        let enter_in_field = self.accepts_input()
+            && self
+                .enter_pressed_components
+                .iter()
+                .any(|field| field.starts_with(&field_prefix));
+        let submit_clicked = self.was_clicked(&id) || self.was_submitted(&id) || enter_in_field;
+        if submit_clicked {
+            self.attempted_forms.insert(id.clone());
+        }
+        let show_errors = self.attempted_forms.contains(&id);
+
+        self.layout_stack.push(LayoutContext::Form);
+        let start_index = self.components.len();
+
+        let valid = self.id_scope(&id, |ui| {
+            let mut form = Form {
+                ui,
+                submit_clicked,
+                show_errors,
+                valid: true,
+            };
+            add_fields(&mut form);
+            form.valid
+        });
+
+        let children = self.components.split_off(start_index);
+        self.layout_stack.pop();
+
+        // A successful submit starts the next round of editing with a clean slate
+        if submit_clicked && valid {
+            self.attempted_forms.remove(&id);
+            self.touched_fields
+                .retain(|field| !field.starts_with(&field_prefix));
+        }
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Form {
+                submit_text: submit_text.to_string(),
+                valid,
+                children,
+            },
+        );
+        self.add_component(component);
+
+        FormResponse {
+            response: self.response(id, submit_clicked, false),
+            valid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pluginui::UiEvent;
+
+    fn login_form(ui: &mut Ui, name: &mut String) -> FormResponse {
+        ui.form("login", "Log in", |form| {
+            form.text_field("Name", name, &[Validator::Required]);
+        })
+    }
+
+    #[test]
+    fn enter_in_field_submits_form() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        let mut name = "alice".to_string();
+
+        let response = login_form(&mut ui, &mut name);
+        assert!(!response.submitted());
+        let field_id = match &ui.get_components()[0].component {
+            UiComponentType::Form { children, .. } => match &children[0].component {
+                UiComponentType::FormField { children, .. } => children[0].id.clone(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+
+        ui.clear_components_only();
+        ui.handle_event(&field_id, UiEvent::Submit);
+        assert!(login_form(&mut ui, &mut name).submitted());
+    }
+
+    #[test]
+    fn forms_with_same_label_are_independent() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        ui.handle_event("form_first", UiEvent::Click);
+
+        let first = ui.form("first", "Save", |_| {});
+        let second = ui.form("second", "Save", |_| {});
+        assert!(first.submitted());
+        assert!(!second.submitted());
+    }
+
+    fn field_errors(ui: &Ui) -> Vec<Option<String>> {
+        let mut errors = Vec::new();
+        if let UiComponentType::Form { children, .. } = &ui.get_components()[0].component {
+            for field in children {
+                if let UiComponentType::FormField { children, .. } = &field.component {
+                    errors.push(children[0].error.clone());
+                }
+            }
+        }
+        errors
+    }
+
+    #[test]
+    fn successful_submit_forgets_touched_fields() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        let mut name = String::new();
+
+        ui.handle_event(
+            "form_login/textedit_0",
+            UiEvent::Change {
+                value: "alice".into(),
+            },
+        );
+        ui.handle_event("form_login", UiEvent::Click);
+        assert!(login_form(&mut ui, &mut name).submitted());
+
+        // The plugin clears its fields after submitting
+        name.clear();
+        ui.clear();
+        login_form(&mut ui, &mut name);
+        assert_eq!(field_errors(&ui), vec![None]);
+    }
+
+    #[test]
+    fn enter_in_later_field_shows_errors_on_earlier_fields() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        let (mut a, mut b) = (String::new(), "filled".to_string());
+        let mut build = |ui: &mut Ui| {
+            ui.form("two", "Save", |form| {
+                form.text_field("A", &mut a, &[Validator::Required]);
+                form.text_field("B", &mut b, &[Validator::Required]);
+            })
+        };
+
+        ui.handle_event("form_two/textedit_1", UiEvent::Submit);
+        let response = build(&mut ui);
+        assert!(!response.submitted());
+        assert_eq!(
+            field_errors(&ui),
+            vec![Some("This field is required".to_string()), None]
+        );
+    }
+}
@@ -5,10 +5,19 @@
 
 mod components;
 mod context;
+mod event;
+mod form;
+mod settings;
 mod ui;
 
-pub use components::{Response, UiComponent, UiComponentType};
-pub use context::{Context, CreationContext};
+pub use components::{
+    Align, Color, ImageSource, InnerResponse, Response, RichText, Style, TableColumn, TableEvent,
+    TableSort, TableState, UiComponent, UiComponentType,
+};
+pub use context::{Context, CreationContext, Theme, UiState};
+pub use event::{UiEvent, UiEventMessage};
+pub use form::{Form, FormResponse, Validator};
+pub use settings::{FieldOptions, SettingsField, SettingsUi};
 pub use ui::PluginUiOption;
 pub use ui::Ui;
 
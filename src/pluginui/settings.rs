@@ -0,0 +1,262 @@
+//! Settings UI generated from plain structs
+//!
+//! `#[derive(PluginSettings)]` (behind the `derive` feature) implements
+//! [`SettingsUi`] for a struct by rendering each field through [`SettingsField`].
+//! On a fieldless enum it implements [`SettingsField`] as a combo box.
+
+use super::ui::Ui;
+
+/// A settings struct that can render itself into a `Ui`
+pub trait SettingsUi {
+    /// Render every field and apply frontend changes; returns whether any field changed
+    fn settings_ui(&mut self, ui: &mut Ui) -> bool;
+}
+
+/// Options for rendering a single settings field
+#[derive(Debug, Clone, Default)]
+pub struct FieldOptions {
+    pub label: String,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub step: Option<f64>,
+}
+
+impl FieldOptions {
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A value that can be edited by a settings widget
+pub trait SettingsField {
+    /// Render the widget and apply frontend changes; returns whether the value changed
+    fn settings_field(&mut self, ui: &mut Ui, options: &FieldOptions) -> bool;
+}
+
+impl SettingsField for String {
+    fn settings_field(&mut self, ui: &mut Ui, options: &FieldOptions) -> bool {
+        ui.horizontal(|ui| {
+            if !options.label.is_empty() {
+                ui.label(options.label.as_str());
+            }
+            ui.text_edit_singleline(self).changed()
+        })
+    }
+}
+
+impl SettingsField for bool {
+    fn settings_field(&mut self, ui: &mut Ui, options: &FieldOptions) -> bool {
+        ui.checkbox(self, &options.label).changed()
+    }
+}
+
+/// Edit a number as `f64`: a slider when both bounds are known, a drag value otherwise
+///
+/// `bounds` are the limits of the field's type. They only clamp edits and are
+/// never used as the snapping origin, so large or unbounded values stay exact.
+fn number_field(
+    ui: &mut Ui,
+    value: &mut f64,
+    options: &FieldOptions,
+    bounds: (f64, f64),
+    default_step: f64,
+) -> bool {
+    let step = options.step.unwrap_or(default_step);
+    let max = options.max.unwrap_or(bounds.1).min(bounds.1);
+
+    let changed = ui.horizontal(|ui| {
+        if !options.label.is_empty() {
+            ui.label(options.label.as_str());
+        }
+        match (options.min, options.max) {
+            (Some(min), Some(_)) => ui
+                .slider_with_step(
+                    value,
+                    min.max(bounds.0)..=max,
+                    Some(step).filter(|s| *s > 0.0),
+                )
+                .changed(),
+            // Drag values snap from the range start, so only pass a range the user gave
+            (Some(min), None) => ui
+                .drag_value(value, Some(min.max(bounds.0)..=max), step)
+                .changed(),
+            (None, _) => ui.drag_value(value, None, step).changed(),
+        }
+    });
+    if changed {
+        *value = value.max(bounds.0).min(max);
+    }
+    changed
+}
+
+macro_rules! impl_settings_field_int {
+    ($($ty:ty),*) => {
+        $(
+            impl SettingsField for $ty {
+                fn settings_field(&mut self, ui: &mut Ui, options: &FieldOptions) -> bool {
+                    let mut value = *self as f64;
+                    let bounds = (<$ty>::MIN as f64, <$ty>::MAX as f64);
+                    let changed = number_field(ui, &mut value, options, bounds, 1.0);
+                    if changed {
+                        *self = value.round() as $ty;
+                    }
+                    changed
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_settings_field_float {
+    ($($ty:ty),*) => {
+        $(
+            impl SettingsField for $ty {
+                fn settings_field(&mut self, ui: &mut Ui, options: &FieldOptions) -> bool {
+                    let mut value = *self as f64;
+                    let bounds = (<$ty>::MIN as f64, <$ty>::MAX as f64);
+                    let changed = number_field(ui, &mut value, options, bounds, 0.0);
+                    if changed {
+                        *self = value as $ty;
+                    }
+                    changed
+                }
+            }
+        )*
+    };
+}
+
+impl_settings_field_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_settings_field_float!(f32, f64);
+
+/// Optional values get a checkbox; the inner widget is shown while it is checked
+impl<T: SettingsField + Default> SettingsField for Option<T> {
+    fn settings_field(&mut self, ui: &mut Ui, options: &FieldOptions) -> bool {
+        ui.horizontal(|ui| {
+            let mut enabled = self.is_some();
+            let mut changed = ui.checkbox(&mut enabled, &options.label).changed();
+            if changed {
+                *self = if enabled { Some(T::default()) } else { None };
+            }
+            if let Some(value) = self {
+                let inner_options = FieldOptions {
+                    label: String::new(),
+                    ..options.clone()
+                };
+                changed |= value.settings_field(ui, &inner_options);
+            }
+            changed
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pluginui::UiEvent;
+
+    fn render<T: SettingsField>(value: &mut T, options: &FieldOptions) -> bool {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        value.settings_field(&mut ui, options)
+    }
+
+    fn render_with_change<T: SettingsField>(
+        value: &mut T,
+        options: &FieldOptions,
+        component_id: &str,
+        new_value: serde_json::Value,
+    ) -> bool {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        ui.handle_event(component_id, UiEvent::Change { value: new_value });
+        value.settings_field(&mut ui, options)
+    }
+
+    #[test]
+    fn int_round_trip_without_change_is_exact() {
+        let options = FieldOptions::default();
+
+        let mut large = (1u64 << 53) + 1;
+        assert!(!render(&mut large, &options));
+        assert_eq!(large, (1u64 << 53) + 1);
+
+        let mut small = 5i64;
+        assert!(!render(&mut small, &options));
+        assert_eq!(small, 5);
+
+        let mut max = u64::MAX;
+        assert!(!render(&mut max, &options));
+        assert_eq!(max, u64::MAX);
+    }
+
+    #[test]
+    fn int_change_is_written_back_and_clamped() {
+        let options = FieldOptions::default();
+
+        let mut value = 5i64;
+        assert!(render_with_change(
+            &mut value,
+            &options,
+            "dragvalue_0",
+            7.into()
+        ));
+        assert_eq!(value, 7);
+
+        let mut value = 5u8;
+        assert!(render_with_change(
+            &mut value,
+            &options,
+            "dragvalue_0",
+            300.into()
+        ));
+        assert_eq!(value, 255);
+
+        let options = FieldOptions {
+            min: Some(0.0),
+            max: Some(10.0),
+            ..Default::default()
+        };
+        let mut value = 5i32;
+        assert!(render_with_change(
+            &mut value,
+            &options,
+            "slider_0",
+            12.into()
+        ));
+        assert_eq!(value, 10);
+    }
+
+    #[test]
+    fn float_round_trip_without_change_is_exact() {
+        let options = FieldOptions::default();
+
+        let mut value = 0.1f32;
+        assert!(!render(&mut value, &options));
+        assert_eq!(value, 0.1f32);
+
+        let mut value = 1.0e300f64;
+        assert!(!render(&mut value, &options));
+        assert_eq!(value, 1.0e300);
+    }
+
+    #[test]
+    fn float_change_is_written_back() {
+        let options = FieldOptions {
+            min: Some(0.0),
+            max: Some(1.0),
+            step: Some(0.1),
+            ..Default::default()
+        };
+        let mut value = 0.5f64;
+        assert!(render_with_change(
+            &mut value,
+            &options,
+            "slider_0",
+            0.7.into()
+        ));
+        assert_eq!(value, 0.7);
+    }
+}
@@ -2,9 +2,17 @@
 
 use crate::PluginHandler;
 
-use super::components::{Response, UiComponent, UiComponentType};
+use super::components::{
+    Color, ImageSource, InnerResponse, Response, RichText, Style, TableColumn, TableEvent,
+    TableState, UiComponent, UiComponentType,
+};
+use super::event::{
+    value_as_bool, value_as_f64, value_as_index, value_as_string, UiEvent, UiEventMessage,
+};
+use serde_json::Value;
 use std::{
     collections::{HashMap, HashSet},
+    ops::RangeInclusive,
     sync::{Arc, Mutex},
 };
 use uuid::Uuid;
@@ -22,7 +30,48 @@ pub struct Ui {
     /// Components that were changed in this frame
     pub(crate) changed_components: HashSet<String>,
     /// UI event data from frontend (component_id -> value)
-    pub(crate) ui_event_data: HashMap<String, String>,
+    pub(crate) ui_event_data: HashMap<String, Value>,
+    /// Whether widgets added in the current scope accept user input
+    pub(crate) enabled: bool,
+    /// Whether widgets added in the current scope are rendered
+    pub(crate) visible: bool,
+    /// Enabled/visible state of the enclosing scopes
+    pub(crate) scope_stack: Vec<ScopeState>,
+    /// Component under the mouse pointer, kept until the pointer leaves it
+    pub(crate) hovered_component: Option<String>,
+    /// Component with keyboard focus, kept until it is blurred
+    pub(crate) focused_component: Option<String>,
+    /// Components that gained focus in this frame
+    pub(crate) gained_focus_components: HashSet<String>,
+    /// Components that lost focus in this frame
+    pub(crate) lost_focus_components: HashSet<String>,
+    /// Components where Enter was pressed in this frame
+    pub(crate) enter_pressed_components: HashSet<String>,
+    /// Open state of collapsing sections, kept across frames (component_id -> open)
+    pub(crate) collapsing_state: HashMap<String, bool>,
+    /// Form fields the user has edited, kept across frames
+    pub(crate) touched_fields: HashSet<String>,
+    /// Forms the user has tried to submit, kept across frames
+    pub(crate) attempted_forms: HashSet<String>,
+    /// Widget ID scopes of the enclosing containers, innermost last
+    pub(crate) id_scopes: Vec<IdScope>,
+}
+
+/// Prefix and widget counter for the IDs generated inside a container
+///
+/// The counter only grows during a frame, so a widget added after a container
+/// never reuses the ID of a widget inside it.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IdScope {
+    prefix: String,
+    next: usize,
+}
+
+/// Enabled/visible state saved when entering a nested scope
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ScopeState {
+    enabled: bool,
+    visible: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +79,14 @@ pub(crate) enum LayoutContext {
     Root,
     Horizontal,
     Vertical,
+    Table,
+    TableRow,
+    Collapsing,
+    Tabs,
+    ScrollArea,
+    Modal,
+    Form,
+    FormField,
 }
 
 impl Ui {
@@ -41,6 +98,18 @@ impl Ui {
             clicked_components: HashSet::new(),
             changed_components: HashSet::new(),
             ui_event_data: HashMap::new(),
+            enabled: true,
+            visible: true,
+            scope_stack: Vec::new(),
+            hovered_component: None,
+            focused_component: None,
+            gained_focus_components: HashSet::new(),
+            lost_focus_components: HashSet::new(),
+            enter_pressed_components: HashSet::new(),
+            collapsing_state: HashMap::new(),
+            touched_fields: HashSet::new(),
+            attempted_forms: HashSet::new(),
+            id_scopes: vec![IdScope::default()],
         }))
     }
 
@@ -49,64 +118,113 @@ impl Ui {
         &self.plugin_id
     }
 
-    /// Add a text label
-    pub fn label(&mut self, text: &str) {
-        let component = UiComponent {
-            id: format!("label_{}", Uuid::new_v4()),
-            component: UiComponentType::Label {
+    /// Add a text label, optionally styled with [`RichText`]
+    pub fn label(&mut self, text: impl Into<RichText>) {
+        let text = text.into();
+        let component = if text.is_plain() {
+            UiComponent::new(
+                format!("label_{}", Uuid::new_v4()),
+                UiComponentType::Label { text: text.text },
+            )
+        } else {
+            UiComponent::new(
+                format!("richtext_{}", Uuid::new_v4()),
+                UiComponentType::RichText {
+                    text: text.text,
+                    bold: text.bold,
+                    italic: text.italic,
+                    monospace: text.monospace,
+                    color: text.color,
+                },
+            )
+        };
+        self.add_component(component);
+    }
+
+    /// Add a hyperlink; the frontend opens `url` in the external browser
+    pub fn hyperlink(&mut self, url: &str, text: &str) -> Response {
+        let id = self.next_id("hyperlink");
+        let was_clicked = self.was_clicked(&id);
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Hyperlink {
+                url: url.to_string(),
+                text: text.to_string(),
+            },
+        );
+        self.add_component(component);
+
+        self.response(id, was_clicked, false)
+    }
+
+    /// Add a block of Markdown, rendered by the frontend
+    pub fn markdown(&mut self, text: &str) {
+        let component = UiComponent::new(
+            format!("markdown_{}", Uuid::new_v4()),
+            UiComponentType::Markdown {
                 text: text.to_string(),
             },
-        };
+        );
+        self.add_component(component);
+    }
+
+    /// Add an image from a file path or a `data:` URI
+    ///
+    /// `size` is `[width, height]` in pixels; `None` keeps the natural size.
+    pub fn image(&mut self, source: impl Into<ImageSource>, size: Option<[f32; 2]>) {
+        let component = UiComponent::new(
+            format!("image_{}", Uuid::new_v4()),
+            UiComponentType::Image {
+                source: source.into(),
+                size,
+            },
+        );
         self.add_component(component);
     }
 
     /// Add a clickable button
     pub fn button(&mut self, text: &str) -> Response {
-        // Use a hash of the text and current component count for stable ID
-        let id = format!(
-            "button_{}_{}",
-            self.components.len(),
-            text.replace(" ", "_")
-        );
-        let component = UiComponent {
-            id: id.clone(),
-            component: UiComponentType::Button {
+        // Use the widget counter and the text for a stable ID
+        let id = format!("{}_{}", self.next_id("button"), text.replace(" ", "_"));
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Button {
                 text: text.to_string(),
-                enabled: true,
+                enabled: self.enabled,
             },
-        };
+        );
         self.add_component(component);
 
         // Check if this component was clicked in this frame
-        let was_clicked = self.clicked_components.contains(&id);
+        let was_clicked = self.was_clicked(&id);
 
         // Return a response with click state
-        Response::new_with_component_and_state(id, was_clicked, false)
+        self.response(id, was_clicked, false)
     }
 
     /// Add a single-line text editor
     pub fn text_edit_singleline(&mut self, value: &mut String) -> Response {
-        // Use a stable ID based on component count for consistent identification
-        let id = format!("textedit_{}", self.components.len());
+        let id = self.next_id("textedit");
 
         // Check if this component was changed and update the value from frontend data
-        let was_changed = self.changed_components.contains(&id);
+        let was_changed = self.was_changed(&id);
         if was_changed {
-            if let Some(new_value) = self.ui_event_data.get(&id) {
-                *value = new_value.clone();
+            if let Some(new_value) = self.event_string(&id) {
+                *value = new_value;
             }
         }
 
-        let component = UiComponent {
-            id: id.clone(),
-            component: UiComponentType::TextEdit {
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::TextEdit {
                 value: value.clone(),
                 hint: String::new(),
             },
-        };
+        );
         self.add_component(component);
 
-        Response::new_with_component_and_state(id, false, was_changed)
+        self.response(id, false, was_changed)
     }
 
     /// Add a combo box (dropdown) widget
@@ -119,24 +237,22 @@ impl Ui {
     where
         T: Clone + PartialEq + ToString,
     {
-        // Use a stable ID based on component count and placeholder
+        // Use the widget counter and the placeholder for a stable ID
         let id = format!(
-            "combo_{}_{}",
-            self.components.len(),
+            "{}_{}",
+            self.next_id("combo"),
             placeholder.replace(" ", "_")
         );
 
         // Check if this component was clicked or changed and update the selection from frontend data
-        let was_clicked = self.clicked_components.contains(&id);
-        let was_changed = self.changed_components.contains(&id);
+        let was_clicked = self.was_clicked(&id);
+        let was_changed = self.was_changed(&id);
         if was_changed {
-            if let Some(new_value) = self.ui_event_data.get(&id) {
-                if let Ok(selection_index) = new_value.parse::<usize>() {
-                    if selection_index < options.len() {
-                        *selected = Some(options[selection_index].clone());
-                    } else {
-                        *selected = None;
-                    }
+            if let Some(selection_index) = self.event_index(&id) {
+                if selection_index < options.len() {
+                    *selected = Some(options[selection_index].clone());
+                } else {
+                    *selected = None;
                 }
             }
         }
@@ -153,44 +269,304 @@ impl Ui {
             *selected = None;
         }
 
-        let component = UiComponent {
-            id: id.clone(),
-            component: UiComponentType::ComboBox {
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::ComboBox {
                 options: options.iter().map(|opt| opt.to_string()).collect(),
                 selected: selected_index,
                 placeholder: placeholder.to_string(),
             },
-        };
+        );
         self.add_component(component);
 
         // Return a response with event states
-        Response::new_with_component_and_state(id, was_clicked, was_changed)
+        self.response(id, was_clicked, was_changed)
     }
 
     /// Add a toggle switch
     pub fn toggle(&mut self, value: &mut bool) -> Response {
-        // Use a stable ID based on component count
-        let id = format!("toggle_{}", self.components.len());
+        let id = self.next_id("toggle");
 
         // Check if this component was clicked and update the value from frontend data
-        let was_clicked = self.clicked_components.contains(&id);
-        let was_changed = self.changed_components.contains(&id);
+        let was_clicked = self.was_clicked(&id);
+        let was_changed = self.was_changed(&id);
         if was_changed {
-            if let Some(new_value) = self.ui_event_data.get(&id) {
-                if let Ok(toggle_value) = new_value.parse::<bool>() {
-                    *value = toggle_value;
+            if let Some(toggle_value) = self.event_bool(&id) {
+                *value = toggle_value;
+            }
+        }
+
+        let component = UiComponent::new(id.clone(), UiComponentType::Toggle { value: *value });
+        self.add_component(component);
+
+        // Return a response with event states
+        self.response(id, was_clicked, was_changed)
+    }
+
+    /// Add a checkbox with a label
+    pub fn checkbox(&mut self, value: &mut bool, label: &str) -> Response {
+        let id = self.next_id("checkbox");
+
+        // Check if this component was clicked and update the value from frontend data
+        let was_clicked = self.was_clicked(&id);
+        let was_changed = self.was_changed(&id);
+        if was_changed {
+            if let Some(checked) = self.event_bool(&id) {
+                *value = checked;
+            }
+        }
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Checkbox {
+                value: *value,
+                label: label.to_string(),
+            },
+        );
+        self.add_component(component);
+
+        self.response(id, was_clicked, was_changed)
+    }
+
+    /// Add a radio button that sets `current` to `alternative` when clicked
+    ///
+    /// Call it once per alternative with the same `current` to build a radio group.
+    pub fn radio_value<T>(&mut self, current: &mut T, alternative: T, label: &str) -> Response
+    where
+        T: PartialEq,
+    {
+        // Use the widget counter and the label for a stable ID
+        let id = format!("{}_{}", self.next_id("radio"), label.replace(" ", "_"));
+
+        let was_clicked = self.was_clicked(&id);
+        let was_changed = was_clicked && *current != alternative;
+        let checked = if was_clicked {
+            *current = alternative;
+            true
+        } else {
+            *current == alternative
+        };
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::RadioButton {
+                checked,
+                label: label.to_string(),
+            },
+        );
+        self.add_component(component);
+
+        self.response(id, was_clicked, was_changed)
+    }
+
+    /// Add a group of selectable values (segmented buttons) with exactly one selected
+    ///
+    /// If `selected` is not one of `options` it is reset to the first option.
+    pub fn selectable_value<T>(&mut self, options: Vec<T>, selected: &mut T) -> Response
+    where
+        T: Clone + PartialEq + ToString,
+    {
+        let id = self.next_id("selectable");
+
+        // Check if this component was changed and update the selection from frontend data
+        let was_clicked = self.was_clicked(&id);
+        let was_changed = self.was_changed(&id);
+        if was_changed {
+            if let Some(selection_index) = self.event_index(&id) {
+                if let Some(option) = options.get(selection_index) {
+                    *selected = option.clone();
                 }
             }
         }
 
-        let component = UiComponent {
-            id: id.clone(),
-            component: UiComponentType::Toggle { value: *value },
+        // Fall back to the first option when the current value is not selectable
+        let selected_index = match options.iter().position(|opt| opt == selected) {
+            Some(index) => index,
+            None => {
+                if let Some(first) = options.first() {
+                    *selected = first.clone();
+                }
+                0
+            }
         };
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::SelectableValue {
+                options: options.iter().map(|opt| opt.to_string()).collect(),
+                selected: selected_index,
+            },
+        );
         self.add_component(component);
 
-        // Return a response with event states
-        Response::new_with_component_and_state(id, was_clicked, was_changed)
+        self.response(id, was_clicked, was_changed)
+    }
+
+    /// Add a slider for a numeric value within `range`
+    pub fn slider(&mut self, value: &mut f64, range: RangeInclusive<f64>) -> Response {
+        self.slider_with_step(value, range, None)
+    }
+
+    /// Add a slider whose value snaps to multiples of `step` (counted from the range start)
+    pub fn slider_with_step(
+        &mut self,
+        value: &mut f64,
+        range: RangeInclusive<f64>,
+        step: Option<f64>,
+    ) -> Response {
+        let id = self.next_id("slider");
+        let (min, max) = ordered_range(&range);
+
+        // Check if this component was changed and update the value from frontend data
+        let was_changed = self.was_changed(&id);
+        if was_changed {
+            if let Some(number) = self.event_f64(&id) {
+                *value = number;
+            }
+        }
+        *value = clamp_to_step(*value, Some(min), Some(max), step);
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Slider {
+                value: *value,
+                min,
+                max,
+                step,
+            },
+        );
+        self.add_component(component);
+
+        self.response(id, false, was_changed)
+    }
+
+    /// Add a numeric input that can be dragged or typed into
+    ///
+    /// `range` limits the value when given; `step` is the drag/arrow increment
+    /// and the granularity the value snaps to.
+    pub fn drag_value(
+        &mut self,
+        value: &mut f64,
+        range: Option<RangeInclusive<f64>>,
+        step: f64,
+    ) -> Response {
+        let id = self.next_id("dragvalue");
+        let (min, max) = match range.as_ref().map(ordered_range) {
+            Some((min, max)) => (Some(min), Some(max)),
+            None => (None, None),
+        };
+
+        // Check if this component was changed and update the value from frontend data
+        let was_changed = self.was_changed(&id);
+        if was_changed {
+            if let Some(number) = self.event_f64(&id) {
+                *value = number;
+            }
+        }
+        *value = clamp_to_step(*value, min, max, Some(step));
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::DragValue {
+                value: *value,
+                min,
+                max,
+                step,
+            },
+        );
+        self.add_component(component);
+
+        self.response(id, false, was_changed)
+    }
+
+    /// Whether widgets added now will accept user input
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable the remaining widgets of the current scope
+    ///
+    /// A scope disabled by its parent cannot be re-enabled from inside.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        let parent_enabled = self.scope_stack.last().is_none_or(|scope| scope.enabled);
+        self.enabled = enabled && parent_enabled;
+    }
+
+    /// Whether widgets added now will be rendered
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Show or hide the remaining widgets of the current scope
+    ///
+    /// A scope hidden by its parent cannot be made visible from inside.
+    pub fn set_visible(&mut self, visible: bool) {
+        let parent_visible = self.scope_stack.last().is_none_or(|scope| scope.visible);
+        self.visible = visible && parent_visible;
+    }
+
+    /// Add widgets that are enabled only if `enabled` is true
+    ///
+    /// Disabled widgets are greyed out and ignore events from the frontend,
+    /// e.g. to block a "Send" button while a request is running.
+    pub fn add_enabled<R>(
+        &mut self,
+        enabled: bool,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        // The contents run in a scope nested inside the disabled one, so
+        // `set_enabled(true)` in them cannot undo the flag
+        self.scope(|ui| {
+            ui.set_enabled(enabled);
+            ui.scope(add_contents)
+        })
+    }
+
+    /// Add widgets that are rendered only if `visible` is true
+    pub fn add_visible<R>(
+        &mut self,
+        visible: bool,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.scope(|ui| {
+            ui.set_visible(visible);
+            ui.scope(add_contents)
+        })
+    }
+
+    /// Run `add_contents` in a nested scope, so `set_enabled`/`set_visible`
+    /// calls inside it do not leak out
+    pub fn scope<R>(&mut self, add_contents: impl FnOnce(&mut Self) -> R) -> R {
+        let saved = ScopeState {
+            enabled: self.enabled,
+            visible: self.visible,
+        };
+        self.scope_stack.push(saved);
+
+        let result = add_contents(self);
+
+        self.scope_stack.pop();
+        self.enabled = saved.enabled;
+        self.visible = saved.visible;
+        result
+    }
+
+    /// Attach style hints to the widgets added in `add_contents`
+    ///
+    /// The style applies to the components added directly in the closure
+    /// (layouts included, but not their children); an inner `with_style`
+    /// takes precedence over an outer one.
+    pub fn with_style<R>(&mut self, style: Style, add_contents: impl FnOnce(&mut Self) -> R) -> R {
+        let start_index = self.components.len();
+
+        let result = self.scope(add_contents);
+
+        for component in &mut self.components[start_index..] {
+            if component.style.is_none() {
+                component.style = Some(style.clone());
+            }
+        }
+        result
     }
 
     /// Create a horizontal layout
@@ -198,17 +574,17 @@ impl Ui {
         self.layout_stack.push(LayoutContext::Horizontal);
         let start_index = self.components.len();
 
-        let result = add_contents(self);
+        let result = self.scope(add_contents);
 
         // Collect components added in this horizontal context
         let children = self.components.split_off(start_index);
 
         if !children.is_empty() {
-            let horizontal_component = UiComponent {
-                id: format!("horizontal_{}", Uuid::new_v4()),
-                component: UiComponentType::Horizontal { children },
-            };
-            self.components.push(horizontal_component);
+            let horizontal_component = UiComponent::new(
+                format!("horizontal_{}", Uuid::new_v4()),
+                UiComponentType::Horizontal { children },
+            );
+            self.add_component(horizontal_component);
         }
 
         self.layout_stack.pop();
@@ -220,28 +596,410 @@ impl Ui {
         self.layout_stack.push(LayoutContext::Vertical);
         let start_index = self.components.len();
 
-        let result = add_contents(self);
+        let result = self.scope(add_contents);
 
         // Collect components added in this vertical context
         let children = self.components.split_off(start_index);
 
         if !children.is_empty() {
-            let vertical_component = UiComponent {
-                id: format!("vertical_{}", Uuid::new_v4()),
-                component: UiComponentType::Vertical { children },
-            };
-            self.components.push(vertical_component);
+            let vertical_component = UiComponent::new(
+                format!("vertical_{}", Uuid::new_v4()),
+                UiComponentType::Vertical { children },
+            );
+            self.add_component(vertical_component);
+        }
+
+        self.layout_stack.pop();
+        result
+    }
+
+    /// Add a progress bar; `fraction` is clamped to 0.0..=1.0
+    ///
+    /// Combine with `refresh_ui` to show live progress of long-running work.
+    pub fn progress_bar(&mut self, fraction: f32, text: Option<&str>) {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        let component = UiComponent::new(
+            format!("progress_{}", Uuid::new_v4()),
+            UiComponentType::ProgressBar {
+                fraction,
+                text: text.map(|t| t.to_string()),
+            },
+        );
+        self.add_component(component);
+    }
+
+    /// Add an indeterminate loading spinner
+    pub fn spinner(&mut self) {
+        let component = UiComponent::new(
+            format!("spinner_{}", Uuid::new_v4()),
+            UiComponentType::Spinner,
+        );
+        self.add_component(component);
+    }
+
+    /// Add a small colored status badge
+    pub fn badge(&mut self, text: &str, color: Color) {
+        let component = UiComponent::new(
+            format!("badge_{}", Uuid::new_v4()),
+            UiComponentType::Badge {
+                text: text.to_string(),
+                color,
+            },
+        );
+        self.add_component(component);
+    }
+
+    /// Add a horizontal separator line
+    pub fn separator(&mut self) {
+        let component = UiComponent::new(
+            format!("separator_{}", Uuid::new_v4()),
+            UiComponentType::Separator,
+        );
+        self.add_component(component);
+    }
+
+    /// Create a collapsible section with a clickable header
+    ///
+    /// Sections start closed; the open state is remembered by the `Ui` across
+    /// frames. `add_contents` only runs while the section is open, and
+    /// `inner` is `None` while it is closed.
+    ///
+    /// `id_source` must be unique among the sections of the enclosing
+    /// container, so sections with the same title keep separate state.
+    pub fn collapsing<R>(
+        &mut self,
+        id_source: &str,
+        title: &str,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> InnerResponse<Option<R>> {
+        // The open state outlives the frame, so the ID must not depend on the
+        // widget counter (which shifts whenever an earlier section opens)
+        let id = self.scoped_id(&format!("collapsing_{}", id_source));
+
+        let was_clicked = self.was_clicked(&id);
+        let was_changed = self.was_changed(&id);
+        if was_changed {
+            if let Some(open) = self.event_bool(&id) {
+                self.collapsing_state.insert(id.clone(), open);
+            }
+        }
+        let open = self.collapsing_state.get(&id).copied().unwrap_or(false);
+
+        self.layout_stack.push(LayoutContext::Collapsing);
+        let start_index = self.components.len();
+
+        let inner = if open {
+            Some(self.id_scope(&id, add_contents))
+        } else {
+            None
+        };
+
+        let children = self.components.split_off(start_index);
+        self.layout_stack.pop();
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Collapsing {
+                title: title.to_string(),
+                open,
+                children,
+            },
+        );
+        self.add_component(component);
+
+        InnerResponse::new(inner, self.response(id, was_clicked, was_changed))
+    }
+
+    /// Create a tab bar; only the contents of the selected tab are built
+    ///
+    /// `add_contents` receives the index of the selected tab.
+    pub fn tabs<R>(
+        &mut self,
+        selected: &mut usize,
+        tabs: &[&str],
+        add_contents: impl FnOnce(&mut Self, usize) -> R,
+    ) -> InnerResponse<R> {
+        let id = self.next_id("tabs");
+
+        let was_clicked = self.was_clicked(&id);
+        let mut was_changed = false;
+        if self.was_changed(&id) {
+            if let Some(index) = self.event_index(&id) {
+                if index < tabs.len() && index != *selected {
+                    *selected = index;
+                    was_changed = true;
+                }
+            }
+        }
+        if *selected >= tabs.len() {
+            *selected = 0;
         }
+        let current = *selected;
+
+        self.layout_stack.push(LayoutContext::Tabs);
+        let start_index = self.components.len();
+
+        let inner = self.id_scope(&id, |ui| add_contents(ui, current));
+
+        let children = self.components.split_off(start_index);
+        self.layout_stack.pop();
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Tabs {
+                tabs: tabs.iter().map(|tab| tab.to_string()).collect(),
+                selected: current,
+                children,
+            },
+        );
+        self.add_component(component);
+
+        InnerResponse::new(inner, self.response(id, was_clicked, was_changed))
+    }
+
+    /// Create a vertically scrollable area no taller than `max_height` pixels
+    pub fn scroll_area<R>(
+        &mut self,
+        max_height: f32,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let id = self.next_id("scrollarea");
+        self.layout_stack.push(LayoutContext::ScrollArea);
+        let start_index = self.components.len();
+
+        let result = self.id_scope(&id, add_contents);
+
+        let children = self.components.split_off(start_index);
+        let component = UiComponent::new(
+            id,
+            UiComponentType::ScrollArea {
+                max_height,
+                children,
+            },
+        );
+        self.add_component(component);
 
         self.layout_stack.pop();
         result
     }
 
+    /// Show a modal dialog while `open` is true
+    ///
+    /// The frontend renders it above the plugin panel; when the user dismisses
+    /// it, `open` is set to false. `add_contents` only runs while it is open.
+    ///
+    /// `id_source` must be unique among the modals of the enclosing container,
+    /// so modals with the same title keep separate events.
+    pub fn modal<R>(
+        &mut self,
+        id_source: &str,
+        title: &str,
+        open: &mut bool,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> Option<R> {
+        // The modal comes and goes, so the ID must not depend on the widget counter
+        let id = self.scoped_id(&format!("modal_{}", id_source));
+
+        if self.was_changed(&id) {
+            if let Some(value) = self.event_bool(&id) {
+                *open = value;
+            }
+        }
+        if !*open {
+            return None;
+        }
+
+        self.layout_stack.push(LayoutContext::Modal);
+        let start_index = self.components.len();
+
+        let result = self.id_scope(&id, add_contents);
+
+        let children = self.components.split_off(start_index);
+        let component = UiComponent::new(
+            id,
+            UiComponentType::Modal {
+                title: title.to_string(),
+                children,
+            },
+        );
+        self.add_component(component);
+
+        self.layout_stack.pop();
+        Some(result)
+    }
+
+    /// Get the user's answer to a dialog opened with `PluginInstanceContext::confirm`
+    ///
+    /// Returns `None` until the frontend reports the choice for `dialog_id`.
+    pub fn confirm_result(&self, dialog_id: &str) -> Option<bool> {
+        if !self.changed_components.contains(dialog_id) {
+            return None;
+        }
+        self.event_bool(dialog_id)
+    }
+
+    /// Create a table with column headers
+    ///
+    /// Add rows inside `add_rows` with [`Ui::table_row`]; components added
+    /// directly become single-cell rows. Row selection and sort requests from
+    /// the frontend update `state` before `add_rows` runs, so the plugin can
+    /// order its data by `state.sort` while building the rows.
+    pub fn table<R>(
+        &mut self,
+        columns: &[TableColumn],
+        state: &mut TableState,
+        add_rows: impl FnOnce(&mut Self) -> R,
+    ) -> Response {
+        let id = self.next_id("table");
+
+        let was_clicked = self.was_clicked(&id);
+        let mut was_changed = false;
+        if self.was_changed(&id) {
+            if let Some(event) = self.event_value(&id).and_then(TableEvent::from_value) {
+                was_changed = event.apply(state);
+            }
+        }
+
+        self.layout_stack.push(LayoutContext::Table);
+        let start_index = self.components.len();
+
+        self.id_scope(&id, add_rows);
+
+        let rows: Vec<UiComponent> = self
+            .components
+            .split_off(start_index)
+            .into_iter()
+            .map(|child| match child.component {
+                UiComponentType::TableRow { .. } => child,
+                _ => UiComponent::new(
+                    format!("tablerow_{}", Uuid::new_v4()),
+                    UiComponentType::TableRow { cells: vec![child] },
+                ),
+            })
+            .collect();
+        self.layout_stack.pop();
+
+        // Drop a selection that no longer points at a row
+        if state.selected_row.is_some_and(|row| row >= rows.len()) {
+            state.selected_row = None;
+        }
+
+        let component = UiComponent::new(
+            id.clone(),
+            UiComponentType::Table {
+                columns: columns.to_vec(),
+                rows,
+                selected_row: state.selected_row,
+                sort: state.sort,
+            },
+        );
+        self.add_component(component);
+
+        self.response(id, was_clicked, was_changed)
+    }
+
+    /// Add a table row; each component added in `add_cells` becomes one cell
+    pub fn table_row<R>(&mut self, add_cells: impl FnOnce(&mut Self) -> R) -> R {
+        self.layout_stack.push(LayoutContext::TableRow);
+        let start_index = self.components.len();
+
+        let result = self.scope(add_cells);
+
+        let cells = self.components.split_off(start_index);
+        let row_component = UiComponent::new(
+            format!("tablerow_{}", Uuid::new_v4()),
+            UiComponentType::TableRow { cells },
+        );
+        self.add_component(row_component);
+
+        self.layout_stack.pop();
+        result
+    }
+
+    /// Next widget ID in the current container, e.g. `textedit_3` or `form_login/textedit_0`
+    pub(crate) fn next_id(&mut self, kind: &str) -> String {
+        let scope = self
+            .id_scopes
+            .last_mut()
+            .expect("the root ID scope is never popped");
+        let name = format!("{}_{}", kind, scope.next);
+        scope.next += 1;
+        self.scoped_id(&name)
+    }
+
+    /// `name` prefixed with the ID of the enclosing container, if any
+    pub(crate) fn scoped_id(&self, name: &str) -> String {
+        match self.id_scopes.last() {
+            Some(scope) if !scope.prefix.is_empty() => format!("{}/{}", scope.prefix, name),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Run `add_contents` in a nested scope whose widget IDs are prefixed with `id`
+    ///
+    /// Children of a container keep their IDs when widgets before the
+    /// container come and go.
+    pub(crate) fn id_scope<R>(&mut self, id: &str, add_contents: impl FnOnce(&mut Self) -> R) -> R {
+        self.id_scopes.push(IdScope {
+            prefix: id.to_string(),
+            next: 0,
+        });
+        let result = self.scope(add_contents);
+        self.id_scopes.pop();
+        result
+    }
+
     /// Internal method to add a component
-    fn add_component(&mut self, component: UiComponent) {
+    pub(crate) fn add_component(&mut self, mut component: UiComponent) {
+        component.enabled = self.enabled;
+        component.visible = self.visible;
         self.components.push(component);
     }
 
+    /// Whether `id` was clicked this frame; disabled or hidden widgets never are
+    pub(crate) fn was_clicked(&self, id: &str) -> bool {
+        self.accepts_input() && self.clicked_components.contains(id)
+    }
+
+    /// Whether `id` received a submit (Enter) this frame; disabled or hidden widgets never do
+    pub(crate) fn was_submitted(&self, id: &str) -> bool {
+        self.accepts_input() && self.enter_pressed_components.contains(id)
+    }
+
+    /// Whether `id` was changed this frame; disabled or hidden widgets never are
+    fn was_changed(&self, id: &str) -> bool {
+        self.accepts_input() && self.changed_components.contains(id)
+    }
+
+    /// Build the response for `id`, including hover and focus state
+    pub(crate) fn response(&self, id: String, clicked: bool, changed: bool) -> Response {
+        let interactive = self.accepts_input();
+        Response {
+            hovered: self.visible && self.hovered_component.as_deref() == Some(id.as_str()),
+            has_focus: interactive && self.focused_component.as_deref() == Some(id.as_str()),
+            gained_focus: interactive && self.gained_focus_components.contains(&id),
+            lost_focus: interactive && self.lost_focus_components.contains(&id),
+            enter_pressed: interactive && self.enter_pressed_components.contains(&id),
+            ..Response::new_with_component_and_state(id, clicked, changed)
+        }
+    }
+
+    pub(crate) fn accepts_input(&self) -> bool {
+        self.enabled && self.visible
+    }
+
+    /// Reset per-frame scope state
+    fn reset_scope(&mut self) {
+        self.enabled = true;
+        self.visible = true;
+        self.scope_stack.clear();
+        self.id_scopes = vec![IdScope::default()];
+    }
+
     /// Get all components for serialization
     pub fn get_components(&self) -> &[UiComponent] {
         &self.components
@@ -250,15 +1008,15 @@ impl Ui {
     /// Clear all components (called at start of each frame)
     pub fn clear(&mut self) {
         self.components.clear();
+        self.reset_scope();
         // Clear event tracking - events should only be active for one frame
-        self.clicked_components.clear();
-        self.changed_components.clear();
-        self.ui_event_data.clear();
+        self.clear_events();
     }
 
     /// Clear only components, keep event tracking for current frame
     pub fn clear_components_only(&mut self) {
         self.components.clear();
+        self.reset_scope();
         // Keep event tracking for this update_ui call
     }
 
@@ -266,34 +1024,201 @@ impl Ui {
     pub fn clear_events(&mut self) {
         self.clicked_components.clear();
         self.changed_components.clear();
+        self.gained_focus_components.clear();
+        self.lost_focus_components.clear();
+        self.enter_pressed_components.clear();
         self.ui_event_data.clear();
     }
 
+    /// Handle a typed UI event (called when frontend sends UI events)
+    ///
+    /// - `Click`: marks the component clicked
+    /// - `Change`: marks it changed and stores the value
+    /// - `Select`: marks it clicked and changed and stores the index
+    /// - `Submit`: reported as `Response::enter_pressed`; submits forms
+    /// - `Hover`/`Leave`/`Focus`/`Blur`: update hover and focus state
+    pub fn handle_event(&mut self, component_id: &str, event: UiEvent) -> bool {
+        let id = component_id.to_string();
+        match event {
+            UiEvent::Click => {
+                self.clicked_components.insert(id);
+            }
+            UiEvent::Change { value } => {
+                self.ui_event_data.insert(id.clone(), value);
+                self.changed_components.insert(id);
+            }
+            UiEvent::Select { index } => {
+                self.ui_event_data.insert(id.clone(), Value::from(index));
+                self.clicked_components.insert(id.clone());
+                self.changed_components.insert(id);
+            }
+            UiEvent::Submit => {
+                self.enter_pressed_components.insert(id);
+            }
+            UiEvent::Hover => {
+                self.hovered_component = Some(id);
+            }
+            UiEvent::Leave => {
+                if self.hovered_component.as_deref() == Some(component_id) {
+                    self.hovered_component = None;
+                }
+            }
+            UiEvent::Focus => {
+                // Focus moving directly between widgets may arrive without a blur
+                if let Some(previous) = self.focused_component.take() {
+                    if previous != component_id {
+                        self.lost_focus_components.insert(previous);
+                    }
+                }
+                self.focused_component = Some(id.clone());
+                self.gained_focus_components.insert(id);
+            }
+            UiEvent::Blur => {
+                if self.focused_component.as_deref() == Some(component_id) {
+                    self.focused_component = None;
+                }
+                self.lost_focus_components.insert(id);
+            }
+        }
+        true
+    }
+
+    /// Handle a UI event message in JSON form, e.g.
+    /// `{"component_id": "slider_3", "type": "change", "value": 0.7}`
+    pub fn handle_event_json(&mut self, json: &str) -> Result<bool, serde_json::Error> {
+        let message = UiEventMessage::from_json(json)?;
+        Ok(self.handle_event(&message.component_id, message.event))
+    }
+
+    /// Handle pointer and keyboard interactions (called when frontend sends them)
+    ///
+    /// `interaction` is one of:
+    /// - `hover` / `leave`: the pointer entered / left the component
+    /// - `focus` / `blur`: the component gained / lost keyboard focus
+    /// - `enter`: Enter was pressed while the component had focus
+    pub fn handle_ui_interaction(&mut self, component_id: &str, interaction: &str) -> bool {
+        let event = match interaction {
+            "hover" => UiEvent::Hover,
+            "leave" => UiEvent::Leave,
+            "focus" => UiEvent::Focus,
+            "blur" => UiEvent::Blur,
+            "enter" => UiEvent::Submit,
+            _ => return false,
+        };
+        self.handle_event(component_id, event)
+    }
+
     /// Handle UI events (called when frontend sends UI events)
+    ///
+    /// Legacy string protocol: the event kind is inferred from the component ID
+    /// prefix and the value is kept as a string. New frontends should send
+    /// typed events through [`Ui::handle_event`] instead.
     pub fn handle_ui_event(&mut self, component_id: &str, value: &str) -> bool {
-        // Store the event data for use in the event loop
-        self.ui_event_data
-            .insert(component_id.to_string(), value.to_string());
+        // Widgets inside containers have path IDs such as `form_login/textedit_0`
+        let name = component_id.rsplit('/').next().unwrap_or(component_id);
+        let has_prefix = |prefixes: &[&str]| prefixes.iter().any(|p| name.starts_with(p));
 
         // Track the event based on component type for the event loop pattern
-        if component_id.starts_with("combo_") {
-            self.clicked_components.insert(component_id.to_string());
-            self.changed_components.insert(component_id.to_string());
-            true
-        } else if component_id.starts_with("button_") {
-            self.clicked_components.insert(component_id.to_string());
-            true
-        } else if component_id.starts_with("textedit_") {
-            self.changed_components.insert(component_id.to_string());
-            true
-        } else if component_id.starts_with("toggle_") {
-            self.clicked_components.insert(component_id.to_string());
-            self.changed_components.insert(component_id.to_string());
-            true
+        let (click, change) = if has_prefix(&[
+            "combo_",
+            "toggle_",
+            "checkbox_",
+            "selectable_",
+            "collapsing_",
+            "tabs_",
+        ]) {
+            (true, true)
+        } else if has_prefix(&["button_", "hyperlink_", "form_", "radio_"]) {
+            (true, false)
+        } else if has_prefix(&["textedit_", "slider_", "dragvalue_", "modal_", "dialog_"]) {
+            (false, true)
+        } else if has_prefix(&["table_"]) {
+            // Row selection counts as a click; both selection and sorting are changes
+            (value.starts_with("select:"), true)
         } else {
-            false
+            return false;
+        };
+
+        if click {
+            self.handle_event(component_id, UiEvent::Click);
+        }
+        if change {
+            let value = Value::String(value.to_string());
+            self.handle_event(component_id, UiEvent::Change { value });
+        }
+        true
+    }
+
+    /// Raw event value received for `id` in this frame
+    pub(crate) fn event_value(&self, id: &str) -> Option<&Value> {
+        self.ui_event_data.get(id)
+    }
+
+    fn event_string(&self, id: &str) -> Option<String> {
+        self.event_value(id).map(value_as_string)
+    }
+
+    fn event_f64(&self, id: &str) -> Option<f64> {
+        self.event_value(id).and_then(value_as_f64)
+    }
+
+    fn event_bool(&self, id: &str) -> Option<bool> {
+        self.event_value(id).and_then(value_as_bool)
+    }
+
+    fn event_index(&self, id: &str) -> Option<usize> {
+        self.event_value(id).and_then(value_as_index)
+    }
+}
+
+/// Snap `value` to the nearest multiple of `step` (relative to `min`) and clamp it to the bounds
+fn clamp_to_step(value: f64, min: Option<f64>, max: Option<f64>, step: Option<f64>) -> f64 {
+    let (min, max) = match (min, max) {
+        (Some(min), Some(max)) if min > max => (Some(max), Some(min)),
+        bounds => bounds,
+    };
+    if !value.is_finite() {
+        return min.unwrap_or(0.0);
+    }
+
+    let mut value = value;
+    if let Some(step) = step.filter(|step| *step > 0.0) {
+        let origin = min.unwrap_or(0.0);
+        let steps = (value - origin) / step;
+        // Values already on a step are kept as sent, so 0.7 doesn't become 0.7000000000000001
+        if (steps - steps.round()).abs() > 1e-9 {
+            let decimals = decimal_places(step).max(decimal_places(origin));
+            let scale = 10f64.powi(decimals);
+            value = ((origin + steps.round() * step) * scale).round() / scale;
         }
     }
+    if let Some(min) = min {
+        value = value.max(min);
+    }
+    if let Some(max) = max {
+        value = value.min(max);
+    }
+    value
+}
+
+/// Order a range so that a reversed one (`10.0..=0.0`) still limits the value
+fn ordered_range(range: &RangeInclusive<f64>) -> (f64, f64) {
+    let (start, end) = (*range.start(), *range.end());
+    if start <= end {
+        (start, end)
+    } else {
+        (end, start)
+    }
+}
+
+/// Number of decimal places needed to write `value` (at most 15)
+fn decimal_places(value: f64) -> i32 {
+    (0..15)
+        .find(|&decimals| {
+            let scale = 10f64.powi(decimals);
+            ((value * scale).round() / scale - value).abs() <= value.abs() * 1e-12
+        })
+        .unwrap_or(15)
 }
 
 /// 插件UI选项 trait
@@ -324,3 +1249,128 @@ impl<T: PluginHandler> PluginUiOption for T {
         plugin_ctx.send_to_frontend("plugin-ui-refreshed", &payload)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_to_step_keeps_values_on_step() {
+        assert_eq!(clamp_to_step(0.7, Some(0.0), Some(1.0), Some(0.1)), 0.7);
+        assert_eq!(clamp_to_step(0.3, Some(0.0), Some(1.0), Some(0.1)), 0.3);
+    }
+
+    #[test]
+    fn clamp_to_step_snaps_to_step_precision() {
+        assert_eq!(clamp_to_step(0.68, Some(0.0), Some(1.0), Some(0.1)), 0.7);
+        assert_eq!(clamp_to_step(7.4, None, None, Some(0.5)), 7.5);
+        assert_eq!(clamp_to_step(12.0, Some(0.0), Some(10.0), Some(1.0)), 10.0);
+    }
+
+    #[test]
+    fn clamp_to_step_accepts_reversed_range() {
+        assert_eq!(clamp_to_step(5.0, Some(10.0), Some(0.0), None), 5.0);
+        assert_eq!(clamp_to_step(-3.0, Some(10.0), Some(0.0), Some(1.0)), 0.0);
+        assert_eq!(ordered_range(&(10.0..=0.0)), (0.0, 10.0));
+    }
+
+    #[test]
+    fn typed_select_reaches_table_state() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        ui.handle_event("table_0", UiEvent::Select { index: 2 });
+
+        let mut state = TableState::default();
+        let response = ui.table(&[], &mut state, |ui| {
+            for row in ["a", "b", "c"] {
+                ui.label(row);
+            }
+        });
+        assert!(response.changed());
+        assert_eq!(state.selected_row, Some(2));
+    }
+
+    #[test]
+    fn sibling_after_container_gets_its_own_id() {
+        let build = |ui: &mut Ui, a: &mut String, b: &mut String, search: &mut String| {
+            ui.form("login", "Log in", |form| {
+                form.text_field("A", a, &[]);
+                form.text_field("B", b, &[]);
+            });
+            ui.tabs(&mut 0, &["One"], |ui, _| {
+                ui.text_edit_singleline(b);
+            });
+            ui.text_edit_singleline(search).component_id.unwrap()
+        };
+
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        let (mut a, mut b, mut search) = (String::new(), String::new(), String::new());
+        let search_id = build(&mut ui, &mut a, &mut b, &mut search);
+        // Field B is `form_login/textedit_1`, so the counters may match without colliding
+        assert_eq!(search_id, "textedit_1");
+
+        ui.clear_components_only();
+        ui.handle_event(
+            &search_id,
+            UiEvent::Change {
+                value: "query".into(),
+            },
+        );
+        build(&mut ui, &mut a, &mut b, &mut search);
+        assert_eq!(search, "query");
+        assert!(a.is_empty() && b.is_empty());
+    }
+
+    #[test]
+    fn add_enabled_cannot_be_undone_inside() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        ui.handle_event("button_0_Send", UiEvent::Click);
+        ui.handle_event("button_1_Show", UiEvent::Click);
+
+        let send = ui.add_enabled(false, |ui| {
+            ui.set_enabled(true);
+            ui.button("Send")
+        });
+        assert!(!send.clicked());
+        assert!(!ui.get_components()[0].enabled);
+
+        let shown = ui.add_visible(false, |ui| {
+            ui.set_visible(true);
+            ui.button("Show")
+        });
+        assert!(!shown.clicked());
+        assert!(!ui.get_components()[1].visible);
+    }
+
+    #[test]
+    fn collapsing_sections_with_same_title_are_independent() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        ui.handle_event("collapsing_network", UiEvent::Change { value: true.into() });
+
+        let network = ui.collapsing("network", "Advanced", |_| {});
+        let display = ui.collapsing("display", "Advanced", |_| {});
+        assert!(network.inner.is_some());
+        assert!(display.inner.is_none());
+    }
+
+    #[test]
+    fn modals_with_same_title_are_independent() {
+        let ui = Ui::new("test".to_string());
+        let mut ui = ui.lock().unwrap();
+        ui.handle_event(
+            "modal_delete_chat",
+            UiEvent::Change {
+                value: false.into(),
+            },
+        );
+
+        let (mut chat_open, mut file_open) = (true, true);
+        ui.modal("delete_chat", "Confirm", &mut chat_open, |_| {});
+        ui.modal("delete_file", "Confirm", &mut file_open, |_| {});
+        assert!(!chat_open);
+        assert!(file_open);
+    }
+}
@@ -0,0 +1,325 @@
+use zeroize::Zeroize;
+
+/// 密钥值（例如 API Key）
+/// Debug/Display 输出会被隐藏，不实现序列化，释放时清零内存
+///
+/// 只有 `Secret` 自己持有的明文会被清零；`Secret::new(&str)` 会复制一份，
+/// 调用者原有的字符串需要自行处理
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// 获取明文，仅在真正需要时调用（例如构造请求头）
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+impl std::fmt::Display for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "file-secrets")]
+pub use file_store::FileSecretStore;
+
+/// 基于文件的加密密钥存储（`file-secrets` 特性），供无头宿主与测试使用
+#[cfg(feature = "file-secrets")]
+mod file_store {
+    use super::Secret;
+    use crate::callbacks::HostCallbacks;
+    use crate::file_callbacks::{
+        chain_free_string, return_string, str_arg, write_atomic, Installed,
+    };
+    use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+    use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+    use std::ffi::c_char;
+    use std::fs;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use zeroize::{Zeroize, Zeroizing};
+
+    const NONCE_LEN: usize = 12;
+
+    /// 文件中保存的密钥，仅在本模块内序列化，释放时随 `Secret` 清零
+    struct StoredSecret(Secret);
+
+    impl Serialize for StoredSecret {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.0.expose())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for StoredSecret {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer).map(|value| StoredSecret(Secret::new(value)))
+        }
+    }
+
+    /// 插件ID -> 键 -> 密钥
+    type Entries = BTreeMap<String, BTreeMap<String, StoredSecret>>;
+
+    /// 加密保存在单个文件中的密钥存储
+    ///
+    /// 文件内容为 `nonce || ChaCha20-Poly1305(JSON)`，每次写入使用新的随机 nonce。
+    /// 主密钥由宿主提供，或由 `with_key_file` 生成并保存在单独的文件中。
+    pub struct FileSecretStore {
+        path: PathBuf,
+        cipher: ChaCha20Poly1305,
+    }
+
+    impl std::fmt::Debug for FileSecretStore {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("FileSecretStore")
+                .field("path", &self.path)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl FileSecretStore {
+        /// 使用 32 字节主密钥，密钥在本函数返回后清零（cipher 内部的副本在释放时清零）
+        pub fn new(path: impl AsRef<Path>, master_key: Zeroizing<[u8; 32]>) -> Self {
+            Self {
+                path: path.as_ref().to_path_buf(),
+                cipher: ChaCha20Poly1305::new(Key::from_slice(master_key.as_slice())),
+            }
+        }
+
+        /// 从密钥文件读取主密钥，文件不存在时生成随机密钥并写入（Unix 下权限为 0600）
+        pub fn with_key_file(
+            path: impl AsRef<Path>,
+            key_path: impl AsRef<Path>,
+        ) -> io::Result<Self> {
+            let key_path = key_path.as_ref();
+            let mut key = Zeroizing::new([0u8; 32]);
+            match fs::read(key_path) {
+                Ok(bytes) => {
+                    let bytes = Zeroizing::new(bytes);
+                    if bytes.len() != key.len() {
+                        return Err(invalid_data("secret key file must be 32 bytes"));
+                    }
+                    key.copy_from_slice(&bytes);
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    let mut generated = ChaCha20Poly1305::generate_key(&mut OsRng);
+                    key.copy_from_slice(&generated);
+                    generated.as_mut_slice().zeroize();
+                    write_atomic(key_path, key.as_slice(), true)?;
+                }
+                Err(e) => return Err(e),
+            }
+            Ok(Self::new(path, key))
+        }
+
+        pub fn get(&self, plugin_id: &str, key: &str) -> io::Result<Option<Secret>> {
+            let mut entries = self.load()?;
+            Ok(entries
+                .get_mut(plugin_id)
+                .and_then(|secrets| secrets.remove(key))
+                .map(|stored| stored.0))
+        }
+
+        pub fn set(&self, plugin_id: &str, key: &str, value: Secret) -> io::Result<()> {
+            let mut entries = self.load()?;
+            entries
+                .entry(plugin_id.to_string())
+                .or_default()
+                .insert(key.to_string(), StoredSecret(value));
+            self.save(&entries)
+        }
+
+        /// 删除密钥，返回密钥是否存在
+        pub fn delete(&self, plugin_id: &str, key: &str) -> io::Result<bool> {
+            let mut entries = self.load()?;
+            let existed = entries
+                .get_mut(plugin_id)
+                .is_some_and(|secrets| secrets.remove(key).is_some());
+            if existed {
+                self.save(&entries)?;
+            }
+            Ok(existed)
+        }
+
+        /// 将本存储注册为 `plugin_id` 的文件密钥回调所用的存储（见 `HostCallbacks::with_file_secrets`）
+        ///
+        /// 每个插件各自注册，可以使用不同的文件；同一插件再次注册时替换原来的存储
+        pub fn install(self, plugin_id: &str) {
+            INSTALLED_STORE.install(plugin_id, self);
+        }
+
+        fn load(&self) -> io::Result<Entries> {
+            let data = match fs::read(&self.path) {
+                Ok(data) => data,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+                Err(e) => return Err(e),
+            };
+            if data.len() < NONCE_LEN {
+                return Err(invalid_data("secret store is truncated"));
+            }
+            let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+            let plaintext = Zeroizing::new(
+                self.cipher
+                    .decrypt(Nonce::from_slice(nonce), ciphertext)
+                    .map_err(|_| invalid_data("failed to decrypt secret store (wrong key?)"))?,
+            );
+            serde_json::from_slice(&plaintext)
+                .map_err(|_| invalid_data("secret store is corrupted"))
+        }
+
+        fn save(&self, entries: &Entries) -> io::Result<()> {
+            let plaintext = Zeroizing::new(serde_json::to_vec(entries)?);
+            let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+            let ciphertext = self
+                .cipher
+                .encrypt(&nonce, plaintext.as_slice())
+                .map_err(|_| invalid_data("failed to encrypt secret store"))?;
+
+            let mut data = nonce.to_vec();
+            data.extend_from_slice(&ciphertext);
+            write_atomic(&self.path, &data, true)
+        }
+    }
+
+    fn invalid_data(message: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+
+    impl HostCallbacks {
+        /// 使用加密文件作为 `plugin_id` 的 secret 回调，适用于无头宿主与测试
+        /// 与 `with_file_storage` 相同，宿主原来的 `free_string` 仍用于其他回调
+        pub fn with_file_secrets(self, plugin_id: &str, store: FileSecretStore) -> Self {
+            store.install(plugin_id);
+            let mut this = chain_free_string(self);
+            this.secret_get = file_secret_get;
+            this.secret_set = file_secret_set;
+            this.secret_delete = file_secret_delete;
+            this
+        }
+    }
+
+    /// 通过 `FileSecretStore::install` 注册的存储，按插件ID区分
+    static INSTALLED_STORE: Installed<FileSecretStore> = Installed::new();
+
+    /// 查找为 `plugin_id` 注册的存储
+    fn with_store<R>(
+        plugin_id: *const c_char,
+        f: impl FnOnce(&FileSecretStore, &str) -> Option<R>,
+    ) -> Option<R> {
+        let plugin_id = unsafe { str_arg(plugin_id)? };
+        INSTALLED_STORE.with(plugin_id, |store| f(store, plugin_id))
+    }
+
+    /// `HostCallbacks::secret_get` 的文件实现
+    extern "C" fn file_secret_get(plugin_id: *const c_char, key: *const c_char) -> *const c_char {
+        let secret = with_store(plugin_id, |store, plugin_id| {
+            store
+                .get(plugin_id, unsafe { str_arg(key)? })
+                .ok()
+                .flatten()
+        });
+        secret.map_or(std::ptr::null(), |secret| return_string(secret.expose()))
+    }
+
+    /// `HostCallbacks::secret_set` 的文件实现
+    extern "C" fn file_secret_set(
+        plugin_id: *const c_char,
+        key: *const c_char,
+        value: *const c_char,
+    ) -> bool {
+        with_store(plugin_id, |store, plugin_id| unsafe {
+            store
+                .set(plugin_id, str_arg(key)?, Secret::new(str_arg(value)?))
+                .ok()
+        })
+        .is_some()
+    }
+
+    /// `HostCallbacks::secret_delete` 的文件实现
+    extern "C" fn file_secret_delete(plugin_id: *const c_char, key: *const c_char) -> bool {
+        with_store(plugin_id, |store, plugin_id| {
+            store.delete(plugin_id, unsafe { str_arg(key)? }).ok()
+        })
+        .is_some()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn round_trip_and_wrong_key() {
+            let path = std::env::temp_dir().join(format!(
+                "plugin-interfaces-secrets-{}.bin",
+                std::process::id()
+            ));
+            let store = FileSecretStore::new(&path, Zeroizing::new([7; 32]));
+            store
+                .set("plugin", "token", Secret::new("hunter2"))
+                .unwrap();
+            assert_eq!(
+                store.get("plugin", "token").unwrap().unwrap().expose(),
+                "hunter2"
+            );
+            assert!(store.delete("plugin", "token").unwrap());
+            assert!(store.get("plugin", "token").unwrap().is_none());
+
+            store
+                .set("plugin", "token", Secret::new("hunter2"))
+                .unwrap();
+            let wrong = FileSecretStore::new(&path, Zeroizing::new([8; 32]));
+            assert!(wrong.get("plugin", "token").is_err());
+            let _ = fs::remove_file(path);
+        }
+
+        #[test]
+        fn each_plugin_keeps_its_own_store() {
+            let path = |name: &str| {
+                std::env::temp_dir().join(format!(
+                    "plugin-interfaces-secrets-{}-{}.bin",
+                    name,
+                    std::process::id()
+                ))
+            };
+            let key_file = path("key");
+            let first = FileSecretStore::with_key_file(path("first"), &key_file).unwrap();
+            first.install("first");
+            let second = FileSecretStore::with_key_file(path("second"), &key_file).unwrap();
+            second.install("second");
+
+            let set = |plugin_id: &str| {
+                let plugin_id = std::ffi::CString::new(plugin_id).unwrap();
+                let key = std::ffi::CString::new("token").unwrap();
+                let value = std::ffi::CString::new("hunter2").unwrap();
+                file_secret_set(plugin_id.as_ptr(), key.as_ptr(), value.as_ptr())
+            };
+            assert!(set("first"));
+            assert!(!set("unknown"));
+
+            let first = FileSecretStore::with_key_file(path("first"), &key_file).unwrap();
+            let second = FileSecretStore::with_key_file(path("second"), &key_file).unwrap();
+            assert!(first.get("first", "token").unwrap().is_some());
+            assert!(second.get("first", "token").unwrap().is_none());
+            for name in ["key", "first"] {
+                let _ = fs::remove_file(path(name));
+            }
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use crate::callbacks::HostCallbacks;
+use crate::file_callbacks::{chain_free_string, return_string, str_arg, write_atomic, Installed};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::ffi::{c_char, CString};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 插件实例的持久化键值存储
+/// 通过 `PluginInstanceContext::storage()` 获取，值以 JSON 形式交给宿主保存
+pub struct Storage<'a> {
+    instance_id: &'a str,
+    callbacks: Option<&'a HostCallbacks>,
+}
+
+impl<'a> Storage<'a> {
+    pub(crate) fn new(instance_id: &'a str, callbacks: Option<&'a HostCallbacks>) -> Self {
+        Self {
+            instance_id,
+            callbacks,
+        }
+    }
+
+    /// 读取原始 JSON 值
+    pub fn get_value(&self, key: &str) -> Option<Value> {
+        let callbacks = self.callbacks?;
+        let instance_id = CString::new(self.instance_id).ok()?;
+        let key = CString::new(key).ok()?;
+        let result_ptr = (callbacks.storage_get)(instance_id.as_ptr(), key.as_ptr());
+        let json = callbacks.take_string(result_ptr)?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// 读取并反序列化为指定类型
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.get_value(key)
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// 序列化并写入
+    pub fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> bool {
+        let Some(callbacks) = self.callbacks else {
+            return false;
+        };
+        let Ok(json) = serde_json::to_string(value) else {
+            return false;
+        };
+        match (
+            CString::new(self.instance_id),
+            CString::new(key),
+            CString::new(json),
+        ) {
+            (Ok(instance_id), Ok(key), Ok(json)) => {
+                (callbacks.storage_set)(instance_id.as_ptr(), key.as_ptr(), json.as_ptr())
+            }
+            _ => false,
+        }
+    }
+
+    /// 删除键
+    pub fn delete(&self, key: &str) -> bool {
+        let Some(callbacks) = self.callbacks else {
+            return false;
+        };
+        match (CString::new(self.instance_id), CString::new(key)) {
+            (Ok(instance_id), Ok(key)) => {
+                (callbacks.storage_delete)(instance_id.as_ptr(), key.as_ptr())
+            }
+            _ => false,
+        }
+    }
+
+    /// 列出所有键
+    pub fn list(&self) -> Vec<String> {
+        let Some(callbacks) = self.callbacks else {
+            return Vec::new();
+        };
+        let Ok(instance_id) = CString::new(self.instance_id) else {
+            return Vec::new();
+        };
+        let result_ptr = (callbacks.storage_list)(instance_id.as_ptr());
+        callbacks
+            .take_string(result_ptr)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// 基于文件的存储实现，供宿主与测试使用
+/// 每个实例的数据保存在 `<数据目录>/<编码后的实例ID>.json`
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    data_dir: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(data_dir: impl AsRef<Path>) -> Self {
+        Self {
+            data_dir: data_dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// 数据目录
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub fn get(&self, instance_id: &str, key: &str) -> io::Result<Option<Value>> {
+        Ok(self.load(instance_id)?.remove(key))
+    }
+
+    pub fn set(&self, instance_id: &str, key: &str, value: Value) -> io::Result<()> {
+        let mut entries = self.load(instance_id)?;
+        entries.insert(key.to_string(), value);
+        self.save(instance_id, &entries)
+    }
+
+    /// 删除键，返回键是否存在
+    pub fn delete(&self, instance_id: &str, key: &str) -> io::Result<bool> {
+        let mut entries = self.load(instance_id)?;
+        let existed = entries.remove(key).is_some();
+        if existed {
+            self.save(instance_id, &entries)?;
+        }
+        Ok(existed)
+    }
+
+    pub fn list(&self, instance_id: &str) -> io::Result<Vec<String>> {
+        Ok(self.load(instance_id)?.into_keys().collect())
+    }
+
+    /// 将本存储注册为 `instance_id` 的文件存储回调所用的存储（见 `HostCallbacks::with_file_storage`）
+    ///
+    /// 每个实例各自注册，可以使用不同的数据目录；同一实例再次注册时替换原来的存储
+    pub fn install(self, instance_id: &str) {
+        INSTALLED_STORAGE.install(instance_id, self);
+    }
+
+    fn path(&self, instance_id: &str) -> PathBuf {
+        // 实例ID可能包含路径分隔符，除字母、数字、`-`、`_` 外的字节按百分号编码，
+        // 保证不同的实例ID对应不同的文件
+        let mut file_name = String::with_capacity(instance_id.len());
+        for byte in instance_id.bytes() {
+            if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+                file_name.push(byte as char);
+            } else {
+                file_name.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        self.data_dir.join(format!("{}.json", file_name))
+    }
+
+    /// 读取实例数据，文件不存在时返回空表；文件损坏时返回错误，避免写入时覆盖原有数据
+    fn load(&self, instance_id: &str) -> io::Result<BTreeMap<String, Value>> {
+        let content = match fs::read_to_string(self.path(instance_id)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn save(&self, instance_id: &str, entries: &BTreeMap<String, Value>) -> io::Result<()> {
+        write_atomic(
+            &self.path(instance_id),
+            &serde_json::to_vec_pretty(entries)?,
+            false,
+        )
+    }
+}
+
+impl HostCallbacks {
+    /// 使用文件存储作为 `instance_id` 的 storage 回调，适用于无头宿主与测试
+    /// 文件回调返回的字符串由本库释放，宿主原来的 `free_string` 仍用于其他回调
+    pub fn with_file_storage(self, instance_id: &str, storage: FileStorage) -> Self {
+        storage.install(instance_id);
+        let mut this = chain_free_string(self);
+        this.storage_get = file_storage_get;
+        this.storage_set = file_storage_set;
+        this.storage_delete = file_storage_delete;
+        this.storage_list = file_storage_list;
+        this
+    }
+}
+
+/// 通过 `FileStorage::install` 注册的存储，按实例ID区分
+static INSTALLED_STORAGE: Installed<FileStorage> = Installed::new();
+
+/// 查找为 `instance_id` 注册的存储
+fn with_storage<R>(
+    instance_id: *const c_char,
+    f: impl FnOnce(&FileStorage, &str) -> Option<R>,
+) -> Option<R> {
+    let instance_id = unsafe { str_arg(instance_id)? };
+    INSTALLED_STORAGE.with(instance_id, |storage| f(storage, instance_id))
+}
+
+/// `HostCallbacks::storage_get` 的文件实现
+extern "C" fn file_storage_get(instance_id: *const c_char, key: *const c_char) -> *const c_char {
+    let value = with_storage(instance_id, |storage, instance_id| {
+        storage
+            .get(instance_id, unsafe { str_arg(key)? })
+            .ok()
+            .flatten()
+    });
+    match value {
+        Some(value) => return_string(&value.to_string()),
+        None => std::ptr::null(),
+    }
+}
+
+/// `HostCallbacks::storage_set` 的文件实现
+extern "C" fn file_storage_set(
+    instance_id: *const c_char,
+    key: *const c_char,
+    value_json: *const c_char,
+) -> bool {
+    with_storage(instance_id, |storage, instance_id| unsafe {
+        let value = serde_json::from_str(str_arg(value_json)?).ok()?;
+        storage.set(instance_id, str_arg(key)?, value).ok()
+    })
+    .is_some()
+}
+
+/// `HostCallbacks::storage_delete` 的文件实现
+extern "C" fn file_storage_delete(instance_id: *const c_char, key: *const c_char) -> bool {
+    with_storage(instance_id, |storage, instance_id| {
+        storage.delete(instance_id, unsafe { str_arg(key)? }).ok()
+    })
+    .is_some()
+}
+
+/// `HostCallbacks::storage_list` 的文件实现
+extern "C" fn file_storage_list(instance_id: *const c_char) -> *const c_char {
+    let keys = with_storage(instance_id, |storage, instance_id| {
+        storage.list(instance_id).ok()
+    });
+    match keys {
+        Some(keys) => return_string(&Value::from(keys).to_string()),
+        None => std::ptr::null(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_storage(name: &str) -> FileStorage {
+        let dir = std::env::temp_dir().join(format!(
+            "plugin-interfaces-storage-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        FileStorage::new(dir)
+    }
+
+    #[test]
+    fn instance_ids_do_not_collide() {
+        let storage = temp_storage("collide");
+        for id in ["a.b", "a_b", "a/b", "a%2Eb"] {
+            storage.set(id, "id", Value::from(id)).unwrap();
+        }
+        for id in ["a.b", "a_b", "a/b", "a%2Eb"] {
+            assert_eq!(storage.get(id, "id").unwrap(), Some(Value::from(id)));
+        }
+        let _ = fs::remove_dir_all(storage.data_dir());
+    }
+
+    #[test]
+    fn corrupt_file_is_not_overwritten() {
+        let storage = temp_storage("corrupt");
+        fs::create_dir_all(storage.data_dir()).unwrap();
+        let path = storage.path("work");
+        fs::write(&path, "{ not json").unwrap();
+
+        assert!(storage.get("work", "key").is_err());
+        assert!(storage.set("work", "key", Value::from(1)).is_err());
+        assert!(storage.delete("work", "key").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ not json");
+        let _ = fs::remove_dir_all(storage.data_dir());
+    }
+
+    #[test]
+    fn each_instance_keeps_its_own_directory() {
+        let first = temp_storage("install-first");
+        let second = temp_storage("install-second");
+        first.clone().install("first");
+        second.clone().install("second");
+
+        let set = |instance_id: &str| {
+            let instance_id = CString::new(instance_id).unwrap();
+            let key = CString::new("key").unwrap();
+            let value = CString::new("1").unwrap();
+            file_storage_set(instance_id.as_ptr(), key.as_ptr(), value.as_ptr())
+        };
+        assert!(set("first"));
+        assert!(set("second"));
+        assert!(!set("unknown"));
+
+        assert_eq!(first.list("first").unwrap(), vec!["key"]);
+        assert_eq!(second.list("second").unwrap(), vec!["key"]);
+        assert!(first.list("second").unwrap().is_empty());
+        let _ = fs::remove_dir_all(first.data_dir());
+        let _ = fs::remove_dir_all(second.data_dir());
+    }
+}
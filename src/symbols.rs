@@ -10,6 +10,8 @@ pub struct PluginWrapper {
 
 /// FFI安全的插件接口
 /// 使用C风格的函数指针而不是trait对象
+///
+/// 结构体布局属于插件ABI：新增函数只能追加在末尾，并同时提升 `PLUGIN_ABI_VERSION`
 #[repr(C)]
 pub struct PluginInterface {
     pub plugin_ptr: *mut std::ffi::c_void,
@@ -29,6 +31,142 @@ pub struct PluginInterface {
     pub set_history: unsafe extern "C" fn(*mut std::ffi::c_void, *const c_char) -> i32,
     pub get_metadata: unsafe extern "C" fn(*mut std::ffi::c_void) -> PluginMetadataFFI,
     pub destroy: unsafe extern "C" fn(*mut std::ffi::c_void),
+    pub init_ui: unsafe extern "C" fn(
+        *mut std::ffi::c_void,
+        *const std::ffi::c_void,
+        *mut std::ffi::c_void,
+    ) -> i32,
+    pub handle_ui_event:
+        unsafe extern "C" fn(*mut std::ffi::c_void, *mut std::ffi::c_void, *const c_char) -> i32,
+    pub on_config_changed:
+        unsafe extern "C" fn(*mut std::ffi::c_void, *const c_char, *const c_char) -> i32,
+    pub save_state: unsafe extern "C" fn(*mut std::ffi::c_void, *mut *mut u8, *mut usize) -> i32,
+    pub restore_state: unsafe extern "C" fn(*mut std::ffi::c_void, *const u8, usize) -> i32,
+    pub free_state: unsafe extern "C" fn(*mut u8, usize),
+    pub on_app_config_changed:
+        unsafe extern "C" fn(*mut std::ffi::c_void, *const c_char, *const c_char) -> i32,
+}
+
+impl PluginInterface {
+    /// 宿主侧的UI初始化入口：在挂载之后、首次 update_ui 之前调用一次
+    /// 通常使用 `CreationContext::from_metadata` 构建创建上下文
+    ///
+    /// # Safety
+    /// `plugin_ptr` 与函数指针必须来自仍然存活的插件实例
+    pub unsafe fn call_init_ui(
+        &self,
+        ctx: &crate::pluginui::CreationContext,
+        ui: &mut crate::pluginui::Ui,
+    ) -> i32 {
+        (self.init_ui)(
+            self.plugin_ptr,
+            ctx as *const crate::pluginui::CreationContext as *const std::ffi::c_void,
+            ui as *mut crate::pluginui::Ui as *mut std::ffi::c_void,
+        )
+    }
+
+    /// 宿主侧的UI更新入口：推进帧计数与时间后调用插件的 update_ui
+    /// 调用后可通过 `ctx.repaint_after()` 获取插件请求的下一次刷新时间
+    ///
+    /// # Safety
+    /// `plugin_ptr` 与函数指针必须来自仍然存活的插件实例
+    pub unsafe fn call_update_ui(
+        &self,
+        ctx: &mut crate::pluginui::Context,
+        ui: &mut crate::pluginui::Ui,
+    ) -> i32 {
+        ctx.begin_frame();
+        (self.update_ui)(
+            self.plugin_ptr,
+            ctx as *const crate::pluginui::Context as *const std::ffi::c_void,
+            ui as *mut crate::pluginui::Ui as *mut std::ffi::c_void,
+        )
+    }
+
+    /// 宿主侧的UI事件入口：将前端发送的类型化事件交给插件的 Ui
+    /// 应在下一次 `call_update_ui` 之前调用；返回 -1 表示事件无法解析
+    ///
+    /// # Safety
+    /// `plugin_ptr` 与函数指针必须来自仍然存活的插件实例
+    pub unsafe fn call_handle_ui_event(
+        &self,
+        ui: &mut crate::pluginui::Ui,
+        event: &crate::pluginui::UiEventMessage,
+    ) -> i32 {
+        let Ok(json) = serde_json::to_string(event) else {
+            return -1;
+        };
+        let Ok(event_json) = std::ffi::CString::new(json) else {
+            return -1;
+        };
+        (self.handle_ui_event)(
+            self.plugin_ptr,
+            ui as *mut crate::pluginui::Ui as *mut std::ffi::c_void,
+            event_json.as_ptr(),
+        )
+    }
+
+    /// 宿主侧的配置变更入口：传入变更前后的 config.toml 内容
+    /// 通常与 `ConfigWatcher::poll` 配合使用
+    ///
+    /// # Safety
+    /// `plugin_ptr` 与函数指针必须来自仍然存活的插件实例
+    pub unsafe fn call_on_config_changed(&self, change: &crate::config::ConfigChange) -> i32 {
+        let (Ok(old_content), Ok(new_content)) = (
+            std::ffi::CString::new(change.old_content.as_str()),
+            std::ffi::CString::new(change.new_content.as_str()),
+        ) else {
+            return -1;
+        };
+        (self.on_config_changed)(self.plugin_ptr, old_content.as_ptr(), new_content.as_ptr())
+    }
+
+    /// 宿主侧的应用配置变更入口：通知订阅了 key 的插件，value 为 None 表示配置项被删除
+    ///
+    /// # Safety
+    /// `plugin_ptr` 与函数指针必须来自仍然存活的插件实例
+    pub unsafe fn call_on_app_config_changed(&self, key: &str, value: Option<&str>) -> i32 {
+        let Ok(key) = std::ffi::CString::new(key) else {
+            return -1;
+        };
+        let value = match value.map(std::ffi::CString::new) {
+            Some(Ok(value)) => Some(value),
+            Some(Err(_)) => return -1,
+            None => None,
+        };
+        (self.on_app_config_changed)(
+            self.plugin_ptr,
+            key.as_ptr(),
+            value
+                .as_ref()
+                .map_or(std::ptr::null(), |value| value.as_ptr()),
+        )
+    }
+
+    /// 宿主侧的状态保存入口：复制插件返回的状态并交还插件释放
+    /// 插件未保存状态或保存失败时返回 None
+    ///
+    /// # Safety
+    /// `plugin_ptr` 与函数指针必须来自仍然存活的插件实例
+    pub unsafe fn call_save_state(&self) -> Option<Vec<u8>> {
+        let mut data: *mut u8 = std::ptr::null_mut();
+        let mut len: usize = 0;
+        if (self.save_state)(self.plugin_ptr, &mut data, &mut len) != 0 || data.is_null() {
+            return None;
+        }
+        // 缓冲区由插件分配，必须由插件释放
+        let state = std::slice::from_raw_parts(data, len).to_vec();
+        (self.free_state)(data, len);
+        Some(state)
+    }
+
+    /// 宿主侧的状态恢复入口
+    ///
+    /// # Safety
+    /// `plugin_ptr` 与函数指针必须来自仍然存活的插件实例
+    pub unsafe fn call_restore_state(&self, state: &[u8]) -> i32 {
+        (self.restore_state)(self.plugin_ptr, state.as_ptr(), state.len())
+    }
 }
 
 /// 插件创建函数类型
@@ -39,9 +177,131 @@ pub type CreatePluginFn = unsafe extern "C" fn() -> *mut PluginInterface;
 /// 销毁插件接口
 pub type DestroyPluginFn = unsafe extern "C" fn(*mut PluginInterface);
 
+/// 插件重新加载失败
+/// 如果旧版本已保存状态，`state` 中保留该状态，宿主可在重试时使用
+#[derive(Debug)]
+pub struct ReloadError {
+    pub message: String,
+    pub state: Option<Vec<u8>>,
+}
+
+impl std::fmt::Display for ReloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to reload plugin: {}", self.message)
+    }
+}
+
+impl std::error::Error for ReloadError {}
+
+/// 重新加载插件（例如更新插件库后），并在新旧版本之间迁移状态
+///
+/// 依次执行：旧版本 `save_state` → `on_dispose` → `destroy_old`，
+/// 然后 `create_new` → `initialize` → `restore_state` → `on_mount`。
+/// 成功后宿主照常调用 `call_init_ui` 重建UI。
+/// 新版本初始化或挂载失败时会被销毁（已初始化的先调用 `on_dispose`），宿主无需再清理。
+///
+/// # Safety
+/// `old` 必须是由 `destroy_old` 对应的库创建的、仍然存活的插件接口，调用后不可再使用；
+/// `create_new` 与 `destroy_new` 必须来自已加载的新版本插件库
+pub unsafe fn reload_plugin(
+    old: *mut PluginInterface,
+    destroy_old: DestroyPluginFn,
+    create_new: CreatePluginFn,
+    destroy_new: DestroyPluginFn,
+    callbacks: HostCallbacks,
+    metadata: &crate::metadata::PluginMetadata,
+) -> Result<*mut PluginInterface, ReloadError> {
+    let state = (*old).call_save_state().filter(|state| !state.is_empty());
+    ((*old).on_dispose)((*old).plugin_ptr);
+    destroy_old(old);
+
+    let new = create_new();
+    if new.is_null() {
+        return Err(ReloadError {
+            message: "create_plugin returned null".to_string(),
+            state,
+        });
+    }
+
+    let metadata_ffi = metadata.to_ffi();
+    let result = ((*new).initialize)((*new).plugin_ptr, callbacks, metadata_ffi);
+    crate::metadata::free_plugin_metadata_ffi(metadata_ffi);
+    if result != 0 {
+        destroy_new(new);
+        return Err(ReloadError {
+            message: "initialize failed".to_string(),
+            state,
+        });
+    }
+
+    // 恢复失败不影响新版本运行，只是从初始状态开始
+    if let Some(state) = &state {
+        if (*new).call_restore_state(state) != 0 {
+            crate::log_warn!("Plugin '{}' failed to restore state", metadata.id);
+        }
+    }
+
+    if ((*new).on_mount)((*new).plugin_ptr) != 0 {
+        ((*new).on_dispose)((*new).plugin_ptr);
+        destroy_new(new);
+        return Err(ReloadError {
+            message: "on_mount failed".to_string(),
+            state,
+        });
+    }
+    Ok(new)
+}
+
 /// 插件导出符号名称
 pub const CREATE_PLUGIN_SYMBOL: &[u8] = b"create_plugin";
 pub const DESTROY_PLUGIN_SYMBOL: &[u8] = b"destroy_plugin";
+pub const PLUGIN_ABI_VERSION_SYMBOL: &[u8] = b"plugin_abi_version";
+
+/// 插件ABI版本，`PluginInterface` 或 `HostCallbacks` 的布局变化时提升
+/// 0.1.x 的插件没有导出 `plugin_abi_version`，视为版本 1
+pub const PLUGIN_ABI_VERSION: u32 = 2;
+
+/// 插件ABI版本函数类型
+/// 插件导出该函数并返回 `PLUGIN_ABI_VERSION`，宿主在调用 `create_plugin` 之前检查
+pub type PluginAbiVersionFn = unsafe extern "C" fn() -> u32;
+
+/// 插件与宿主的ABI版本不一致
+#[derive(Debug)]
+pub struct AbiVersionError {
+    pub plugin: u32,
+    pub host: u32,
+}
+
+impl std::fmt::Display for AbiVersionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Plugin ABI version {} does not match host ABI version {}",
+            self.plugin, self.host
+        )
+    }
+}
+
+impl std::error::Error for AbiVersionError {}
+
+/// 检查插件的ABI版本是否与宿主一致
+/// `abi_version` 为插件导出的 `plugin_abi_version`，未导出时传 `None`
+///
+/// # Safety
+/// `abi_version` 必须来自已加载且仍未卸载的插件库
+pub unsafe fn check_abi_version(
+    abi_version: Option<PluginAbiVersionFn>,
+) -> Result<(), AbiVersionError> {
+    let plugin = abi_version.map_or(1, |abi_version| abi_version());
+    if plugin == PLUGIN_ABI_VERSION {
+        Ok(())
+    } else {
+        Err(AbiVersionError {
+            plugin,
+            host: PLUGIN_ABI_VERSION,
+        })
+    }
+}
 
 /// 从PluginHandler trait对象创建FFI安全的插件接口
 /// 这个函数帮助插件开发者将trait对象转换为FFI安全的接口
@@ -76,6 +336,23 @@ pub fn create_plugin_interface_from_handler(
         }
     }
 
+    unsafe extern "C" fn init_ui_wrapper(
+        ptr: *mut std::ffi::c_void,
+        ctx_ptr: *const std::ffi::c_void,
+        ui_ptr: *mut std::ffi::c_void,
+    ) -> i32 {
+        let wrapper = &mut *(ptr as *mut PluginWrapper);
+        let ctx = &*(ctx_ptr as *const crate::pluginui::CreationContext);
+        let ui = &mut *(ui_ptr as *mut crate::pluginui::Ui);
+
+        if let Some(plugin_context) = &wrapper.context {
+            wrapper.handler.init_ui(ctx, ui, plugin_context);
+            0
+        } else {
+            -1
+        }
+    }
+
     unsafe extern "C" fn update_ui_wrapper(
         ptr: *mut std::ffi::c_void,
         ctx_ptr: *const std::ffi::c_void,
@@ -91,6 +368,24 @@ pub fn create_plugin_interface_from_handler(
         0
     }
 
+    unsafe extern "C" fn handle_ui_event_wrapper(
+        _ptr: *mut std::ffi::c_void,
+        ui_ptr: *mut std::ffi::c_void,
+        event_json: *const c_char,
+    ) -> i32 {
+        if ui_ptr.is_null() || event_json.is_null() {
+            return -1;
+        }
+        let ui = &mut *(ui_ptr as *mut crate::pluginui::Ui);
+        let event_str = CStr::from_ptr(event_json).to_string_lossy();
+
+        // 解析类型化的UI事件 JSON
+        match ui.handle_event_json(&event_str) {
+            Ok(_) => 0,
+            Err(_) => -1,
+        }
+    }
+
     unsafe extern "C" fn on_mount_wrapper(ptr: *mut std::ffi::c_void) -> i32 {
         let wrapper = &mut *(ptr as *mut PluginWrapper);
 
@@ -140,6 +435,125 @@ pub fn create_plugin_interface_from_handler(
         }
     }
 
+    unsafe extern "C" fn on_config_changed_wrapper(
+        ptr: *mut std::ffi::c_void,
+        old_content: *const c_char,
+        new_content: *const c_char,
+    ) -> i32 {
+        let wrapper = &mut *(ptr as *mut PluginWrapper);
+        if old_content.is_null() || new_content.is_null() {
+            return -1;
+        }
+        let Some(context) = &mut wrapper.context else {
+            return -1;
+        };
+
+        // 解析变更前后的配置并应用实例覆盖，旧配置解析失败时视为与新配置相同
+        let resolve = |content: *const c_char| {
+            let content = CStr::from_ptr(content).to_string_lossy();
+            content
+                .parse::<crate::config::PluginConfig>()
+                .and_then(|config| config.resolve_for_instance(&context.instance_id))
+        };
+        let mut new = match resolve(new_content) {
+            Ok(config) => config,
+            Err(e) => {
+                crate::log_warn!("Failed to parse changed config: {}", e);
+                return -1;
+            }
+        };
+        let mut old = resolve(old_content).unwrap_or_else(|_| new.clone());
+        old.config_path = context.metadata.config_path.clone();
+        new.config_path = context.metadata.config_path.clone();
+
+        if let Err(e) = context.apply_config(&new) {
+            crate::log_warn!("{}", e);
+            return -1;
+        }
+        match wrapper.handler.on_config_changed(&old, &new, context) {
+            Ok(_) => 0,
+            Err(_) => -1,
+        }
+    }
+
+    unsafe extern "C" fn on_app_config_changed_wrapper(
+        ptr: *mut std::ffi::c_void,
+        key: *const c_char,
+        value: *const c_char,
+    ) -> i32 {
+        let wrapper = &mut *(ptr as *mut PluginWrapper);
+        if key.is_null() {
+            return -1;
+        }
+        let key_str = CStr::from_ptr(key).to_string_lossy();
+        let value_str = if value.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(value).to_string_lossy())
+        };
+
+        if let Some(plugin_context) = &wrapper.context {
+            match wrapper.handler.on_app_config_changed(
+                &key_str,
+                value_str.as_deref(),
+                plugin_context,
+            ) {
+                Ok(_) => 0,
+                Err(_) => -1,
+            }
+        } else {
+            -1
+        }
+    }
+
+    unsafe extern "C" fn save_state_wrapper(
+        ptr: *mut std::ffi::c_void,
+        data: *mut *mut u8,
+        len: *mut usize,
+    ) -> i32 {
+        let wrapper = &mut *(ptr as *mut PluginWrapper);
+
+        if let Some(plugin_context) = &wrapper.context {
+            // 转为 Box<[u8]> 使容量等于长度，便于 free_state 释放
+            let state = wrapper
+                .handler
+                .save_state(plugin_context)
+                .into_boxed_slice();
+            *len = state.len();
+            *data = Box::into_raw(state) as *mut u8;
+            0
+        } else {
+            -1
+        }
+    }
+
+    unsafe extern "C" fn restore_state_wrapper(
+        ptr: *mut std::ffi::c_void,
+        data: *const u8,
+        len: usize,
+    ) -> i32 {
+        let wrapper = &mut *(ptr as *mut PluginWrapper);
+        if data.is_null() {
+            return -1;
+        }
+        let state = std::slice::from_raw_parts(data, len);
+
+        if let Some(plugin_context) = &wrapper.context {
+            match wrapper.handler.restore_state(state, plugin_context) {
+                Ok(_) => 0,
+                Err(_) => -1,
+            }
+        } else {
+            -1
+        }
+    }
+
+    unsafe extern "C" fn free_state_wrapper(data: *mut u8, len: usize) {
+        if !data.is_null() {
+            let _ = Box::from_raw(std::ptr::slice_from_raw_parts_mut(data, len));
+        }
+    }
+
     unsafe extern "C" fn handle_message_wrapper(
         ptr: *mut std::ffi::c_void,
         message: *const c_char,
@@ -222,11 +636,18 @@ pub fn create_plugin_interface_from_handler(
     let interface = PluginInterface {
         plugin_ptr: wrapper_ptr,
         initialize: initialize_wrapper,
+        init_ui: init_ui_wrapper,
         update_ui: update_ui_wrapper,
+        handle_ui_event: handle_ui_event_wrapper,
         on_mount: on_mount_wrapper,
         on_dispose: on_dispose_wrapper,
         on_connect: on_connect_wrapper,
         on_disconnect: on_disconnect_wrapper,
+        on_config_changed: on_config_changed_wrapper,
+        on_app_config_changed: on_app_config_changed_wrapper,
+        save_state: save_state_wrapper,
+        restore_state: restore_state_wrapper,
+        free_state: free_state_wrapper,
         handle_message: handle_message_wrapper,
         set_history: set_history_wrapper,
         get_metadata: get_metadata_wrapper,
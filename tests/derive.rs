@@ -0,0 +1,214 @@
+//! `#[derive(PluginSettings)]` rendered through a real `Ui`
+
+use plugin_interfaces::pluginui::{
+    SettingsField, SettingsUi, UiComponent, UiComponentType, UiEvent,
+};
+use plugin_interfaces::{PluginSettings, Ui};
+use serde_json::Value;
+
+#[derive(PluginSettings, Default, Debug, PartialEq)]
+struct ChatSettings {
+    nickname: String,
+    stream: bool,
+    #[settings(label = "Temperature", min = 0.0, max = 2.0, step = 0.1)]
+    temperature: f64,
+    max_tokens: Option<u32>,
+    model: Model,
+    #[settings(skip)]
+    cache: Vec<String>,
+}
+
+#[derive(PluginSettings, Default, Debug, Clone, Copy, PartialEq)]
+enum Model {
+    #[default]
+    #[settings(label = "GPT-4o")]
+    Gpt4o,
+    Claude,
+    OpenSource,
+}
+
+/// Widgets in render order, with layout containers flattened
+fn widgets(components: &[UiComponent]) -> Vec<&UiComponent> {
+    let mut flat = Vec::new();
+    for component in components {
+        match &component.component {
+            UiComponentType::Horizontal { children } | UiComponentType::Vertical { children } => {
+                flat.extend(widgets(children))
+            }
+            _ => flat.push(component),
+        }
+    }
+    flat
+}
+
+/// Render one frame, optionally with frontend events, and return the components
+fn frame<T: SettingsUi>(
+    ui: &mut Ui,
+    settings: &mut T,
+    events: Vec<(String, UiEvent)>,
+) -> (bool, Vec<UiComponent>) {
+    for (id, event) in events {
+        ui.handle_event(&id, event);
+    }
+    ui.clear_components_only();
+    let changed = settings.settings_ui(ui);
+    ui.clear_events();
+    (changed, ui.get_components().to_vec())
+}
+
+fn id_of(components: &[UiComponent], matches: impl Fn(&UiComponentType) -> bool) -> String {
+    widgets(components)
+        .into_iter()
+        .find(|widget| matches(&widget.component))
+        .map(|widget| widget.id.clone())
+        .expect("widget not rendered")
+}
+
+#[test]
+fn struct_renders_every_field_except_skipped_ones() {
+    let ui = Ui::new("derive".to_string());
+    let mut ui = ui.lock().unwrap();
+    let mut settings = ChatSettings::default();
+    let (changed, components) = frame(&mut ui, &mut settings, Vec::new());
+    assert!(!changed);
+
+    let rendered: Vec<&UiComponentType> = widgets(&components)
+        .into_iter()
+        .map(|widget| &widget.component)
+        .collect();
+    let texts: Vec<&str> = rendered
+        .iter()
+        .filter_map(|component| match component {
+            UiComponentType::Label { text } | UiComponentType::Checkbox { label: text, .. } => {
+                Some(text.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        ["Nickname", "Stream", "Temperature", "Max tokens", "Model"]
+    );
+
+    assert!(rendered.iter().any(|component| matches!(
+        component,
+        UiComponentType::Slider { min, max, step: Some(step), .. }
+            if *min == 0.0 && *max == 2.0 && (*step - 0.1).abs() < 1e-9
+    )));
+    assert!(rendered.iter().any(|component| matches!(
+        component,
+        UiComponentType::ComboBox { options, selected: Some(0), .. }
+            if options == &["GPT-4o", "Claude", "Open source"]
+    )));
+    // The optional field only shows its inner widget while it is checked
+    assert!(!rendered
+        .iter()
+        .any(|component| matches!(component, UiComponentType::DragValue { .. })));
+}
+
+#[test]
+fn frontend_changes_update_the_struct() {
+    let ui = Ui::new("derive".to_string());
+    let mut ui = ui.lock().unwrap();
+    let mut settings = ChatSettings {
+        cache: vec!["kept".to_string()],
+        ..Default::default()
+    };
+    let (_, components) = frame(&mut ui, &mut settings, Vec::new());
+
+    let text_id = id_of(&components, |c| {
+        matches!(c, UiComponentType::TextEdit { .. })
+    });
+    let stream_id = id_of(
+        &components,
+        |c| matches!(c, UiComponentType::Checkbox { label, .. } if label == "Stream"),
+    );
+    let slider_id = id_of(&components, |c| matches!(c, UiComponentType::Slider { .. }));
+    let max_tokens_id = id_of(
+        &components,
+        |c| matches!(c, UiComponentType::Checkbox { label, .. } if label == "Max tokens"),
+    );
+    let change = |value: Value| UiEvent::Change { value };
+    let (changed, components) = frame(
+        &mut ui,
+        &mut settings,
+        vec![
+            (text_id, change(Value::from("bot"))),
+            (stream_id, change(Value::from(true))),
+            (slider_id, change(Value::from(1.5))),
+            (max_tokens_id, change(Value::from(true))),
+        ],
+    );
+    assert!(changed);
+
+    let drag_id = id_of(&components, |c| {
+        matches!(c, UiComponentType::DragValue { .. })
+    });
+    let (changed, _) = frame(
+        &mut ui,
+        &mut settings,
+        vec![(drag_id, change(Value::from(256)))],
+    );
+    assert!(changed);
+
+    assert_eq!(
+        settings,
+        ChatSettings {
+            nickname: "bot".to_string(),
+            stream: true,
+            temperature: 1.5,
+            max_tokens: Some(256),
+            model: Model::Gpt4o,
+            cache: vec!["kept".to_string()],
+        }
+    );
+}
+
+#[test]
+fn enum_select_round_trips() {
+    let ui = Ui::new("derive".to_string());
+    let mut ui = ui.lock().unwrap();
+    let mut model = Model::default();
+    let options = plugin_interfaces::pluginui::FieldOptions::new("Model");
+    let render = |ui: &mut Ui, model: &mut Model, events: Vec<(String, UiEvent)>| {
+        for (id, event) in events {
+            ui.handle_event(&id, event);
+        }
+        ui.clear_components_only();
+        let changed = model.settings_field(ui, &options);
+        ui.clear_events();
+        (changed, ui.get_components().to_vec())
+    };
+
+    let (_, components) = render(&mut ui, &mut model, Vec::new());
+    let combo_id = id_of(&components, |c| {
+        matches!(c, UiComponentType::ComboBox { .. })
+    });
+
+    for (index, expected) in [
+        (2, Model::OpenSource),
+        (1, Model::Claude),
+        (0, Model::Gpt4o),
+    ] {
+        let (changed, components) = render(
+            &mut ui,
+            &mut model,
+            vec![(combo_id.clone(), UiEvent::Select { index })],
+        );
+        assert!(changed);
+        assert_eq!(model, expected);
+        assert!(widgets(&components).iter().any(|widget| matches!(
+            widget.component,
+            UiComponentType::ComboBox { selected: Some(selected), .. } if selected == index
+        )));
+    }
+
+    // Selecting the current variant again is not a change
+    let (changed, _) = render(
+        &mut ui,
+        &mut model,
+        vec![(combo_id, UiEvent::Select { index: 0 })],
+    );
+    assert!(!changed);
+    assert_eq!(model, Model::Gpt4o);
+}